@@ -22,6 +22,7 @@ let sendArmTimer = null;
 let dashLastHeight = -1;
 let dashLastTxCount = -1;
 let activeWalletName = 'wallet.dat';
+let pendingPaymentRequest = null;
 
 function walletKey(base) {
   return base + ':' + activeWalletName;
@@ -34,14 +35,13 @@ function setActiveWalletName(name) {
 }
 
 function migrateLocalStorageKeys() {
-  // One-time migration: move unnamespaced txCache/addressBook to the active wallet's namespace
+  // History is cached encrypted by the backend now; drop the old plaintext copies
   try {
-    var oldTx = localStorage.getItem('txCache');
-    if (oldTx && !localStorage.getItem(walletKey('txCache'))) {
-      localStorage.setItem(walletKey('txCache'), oldTx);
-    }
-    localStorage.removeItem('txCache');
+    Object.keys(localStorage).forEach(function (k) {
+      if (k === 'txCache' || k.indexOf('txCache:') === 0) localStorage.removeItem(k);
+    });
   } catch (_) {}
+  // One-time migration: move the unnamespaced addressBook to the active wallet's namespace
   try {
     var oldBook = localStorage.getItem('addressBook');
     if (oldBook && !localStorage.getItem(walletKey('addressBook'))) {
@@ -134,8 +134,17 @@ function playTada() {
   playNote(2637.0, 0.18, 0.25, 'sine',     0.04);
 }
 
+// The public API from withGlobalTauri; the CSP keeps other scripts out and
+// the capability file limits it to our own commands plus event listening
+const tauri = window.__TAURI__;
+
 function invoke(cmd, args) {
-  return window.__TAURI__.core.invoke(cmd, args);
+  return tauri.core.invoke(cmd, args);
+}
+
+// Resolves to the unlisten function
+function listen(event, handler) {
+  return tauri.event.listen(event, handler);
 }
 
 // --- API Client (proxied through Rust, no CORS) ---
@@ -219,13 +228,14 @@ function navigate(view) {
 async function loadView(view) {
   try {
     switch (view) {
-      case 'dashboard': await loadDashboard(); break;
+      case 'dashboard': await loadDashboard(); await refreshNetworkAlerts(); break;
       case 'send': renderAddressBook(); break;
       case 'receive': await loadReceive(); break;
       case 'history': await loadHistory(); break;
       case 'mining': await loadMining(); break;
       case 'network': await loadNetwork(); break;
-      case 'settings': await loadWalletList(); break;
+      case 'explorer': await loadExplorer(); break;
+      case 'settings': await loadWalletList(); await loadAutoLockSettings(); await loadReauthSettings(); await loadHealthSettings(); await loadProxySettings(); await loadConfirmationSettings(); await loadUriHandler(); await loadMiningSchedule(); await loadThrottleSettings(); break;
     }
   } catch (e) {
     console.error('Error loading ' + view + ':', e);
@@ -234,6 +244,23 @@ async function loadView(view) {
 
 // --- Dashboard ---
 
+function renderDashBalance(balance) {
+  document.getElementById('dash-balance').textContent = formatBNTShort(balance.spendable);
+  document.getElementById('dash-pending').textContent = formatBNTShort(balance.pending);
+  document.getElementById('dash-total').textContent = formatBNTShort(balance.total);
+  document.getElementById('pending-label').classList.toggle('has-pending', balance.pending > 0);
+  document.getElementById('dash-balance').classList.toggle('cached', !!balance.cached);
+}
+
+// Last known balance from the encrypted wallet cache, shown until the
+// daemon answers
+async function paintCachedBalance() {
+  try {
+    var snapshot = await invoke('get_cached_wallet');
+    if (snapshot && snapshot.balance) renderDashBalance(Object.assign({ cached: true }, snapshot.balance));
+  } catch (_) {}
+}
+
 async function loadDashboard() {
   try {
     const status = await api('/api/status');
@@ -253,13 +280,9 @@ async function loadDashboard() {
   }
 
   try {
-    const balance = await api('/api/wallet/balance');
-    document.getElementById('dash-balance').textContent = formatBNTShort(balance.spendable);
-    document.getElementById('dash-pending').textContent = formatBNTShort(balance.pending);
-    document.getElementById('dash-total').textContent = formatBNTShort(balance.total);
-    document.getElementById('pending-label').classList.toggle('has-pending', balance.pending > 0);
+    renderDashBalance(await invoke('get_balance'));
   } catch (e) {
-    // balance may fail during sync, that's ok
+    // balance may fail during sync when nothing is cached yet, that's ok
   }
 
   // Only re-fetch history when chain height changes (avoid hammering API every poll tick)
@@ -267,16 +290,10 @@ async function loadDashboard() {
     var statusHeight = parseInt(document.getElementById('dash-height').textContent.replace(/,/g, '')) || 0;
     if (statusHeight !== dashLastHeight) {
       dashLastHeight = statusHeight;
-      var data = await api('/api/wallet/history');
+      var data = await invoke('get_history');
       var container = document.getElementById('dash-recent-tx');
-      var hasOutputsArray = data && Array.isArray(data.outputs);
-      var outputs = hasOutputsArray ? data.outputs : null;
-      var fromCache = false;
-      if (hasOutputsArray) {
-        try { localStorage.setItem(walletKey('txCache'), JSON.stringify(outputs)); } catch (_) {}
-      } else {
-        try { outputs = JSON.parse(localStorage.getItem(walletKey('txCache')) || 'null'); fromCache = true; } catch (_) {}
-      }
+      var outputs = data.outputs;
+      var fromCache = data.cached;
       if (!outputs || outputs.length === 0) {
         container.innerHTML = '<div class="empty">No transactions yet</div>';
         dashLastTxCount = 0;
@@ -318,12 +335,34 @@ async function loadDashboard() {
 async function loadReceive() {
   const data = await api('/api/wallet/address');
   document.getElementById('receive-address').textContent = data.address;
+  await renderReceiveQR();
+}
+
+// Plain address by default; a blocknet: payment link once an amount is set
+async function receiveQRText() {
+  var address = document.getElementById('receive-address').textContent;
+  var amount = parseFloat(document.getElementById('receive-amount').value);
+  if (!(amount > 0)) return address;
+  return invoke('build_payment_uri', {
+    address: address,
+    amount: Math.round(amount * 100000000),
+    label: null,
+    message: null,
+  });
+}
 
-  if (typeof qrcode === 'function') {
-    var svgHtml = renderQRSvg(data.address);
-    document.getElementById('qr-container').innerHTML = svgHtml;
-    document.getElementById('qr-overlay-inner').innerHTML = svgHtml;
+async function renderReceiveQR() {
+  if (typeof qrcode !== 'function') return;
+  var text;
+  try {
+    text = await receiveQRText();
+  } catch (e) {
+    console.error('Payment link error:', e);
+    text = document.getElementById('receive-address').textContent;
   }
+  var svgHtml = renderQRSvg(text);
+  document.getElementById('qr-container').innerHTML = svgHtml;
+  document.getElementById('qr-overlay-inner').innerHTML = svgHtml;
 }
 
 function renderQRSvg(text) {
@@ -367,17 +406,11 @@ function dismissQROverlay() {
 // --- History ---
 
 async function loadHistory() {
-  const data = await api('/api/wallet/history');
+  const data = await invoke('get_history');
   const container = document.getElementById('history-list');
 
-  var hasOutputsArray = data && Array.isArray(data.outputs);
-  var outputs = hasOutputsArray ? data.outputs : null;
-  var fromCache = false;
-  if (hasOutputsArray) {
-    try { localStorage.setItem(walletKey('txCache'), JSON.stringify(outputs)); } catch (_) {}
-  } else {
-    try { outputs = JSON.parse(localStorage.getItem(walletKey('txCache')) || 'null'); fromCache = true; } catch (_) {}
-  }
+  var outputs = data.outputs;
+  var fromCache = data.cached;
 
   if (!outputs || outputs.length === 0) {
     renderHistoryBalanceSparkline([]);
@@ -392,87 +425,167 @@ async function loadHistory() {
 
   container.innerHTML = sorted.map(o => {
     const typeLabel = o.is_coinbase ? 'mining reward' : (o.spent ? 'sent' : 'received');
-    return '<div class="history-row' + (o.spent ? ' spent' : '') + '" data-txid="' + o.txid + '">' +
+    const memo = o.send && o.send.memo ? o.send.memo : '';
+    const tags = [o.label, o.note, memo].filter(Boolean).map(escapeHtml).join(' ; ');
+    return '<div class="history-row' + (o.spent ? ' spent' : '') + '" data-txid="' + o.txid + '" data-index="' + o.output_index + '">' +
       '<div class="history-amount ' + (o.spent ? '' : 'g') + '">' +
         (o.spent ? '-' : '+') + formatBNT(o.amount) + ' BNT' +
       '</div>' +
       '<div class="history-meta">' +
         '<span class="d">Block ' + o.block_height + '</span>' +
         '<span class="' + (o.spent ? 'd' : 'g') + '">' + typeLabel + '</span>' +
+        confirmationBadge(o) +
+        '<button class="history-label-btn" title="Label this output">' + (o.label ? 'edit label' : 'label') + '</button>' +
       '</div>' +
+      (tags ? '<div class="history-label">' + tags + '</div>' : '') +
       '<div class="history-tx d">' + o.txid.substring(0, 24) + '...</div>' +
     '</div>';
   }).join('');
 
+  container.querySelectorAll('.history-label-btn').forEach(btn => {
+    btn.addEventListener('click', (ev) => {
+      ev.stopPropagation();
+      var row = btn.closest('.history-row');
+      var entry = outputs.find(o => o.txid === row.dataset.txid && String(o.output_index) === row.dataset.index);
+      if (entry) startHistoryLabelEdit(row, entry);
+    });
+  });
+
   if (fromCache) {
     container.insertAdjacentHTML('afterbegin', '<div class="tx-cache-note d">Showing cached history ; resyncing blockchain</div>');
   }
 
   container.querySelectorAll('.history-row[data-txid]').forEach(row => {
     row.addEventListener('click', () => {
-      window.__TAURI__.shell.open('https://explorer.blocknetcrypto.com/tx/' + row.dataset.txid);
+      showExplorer('tx', row.dataset.txid);
     });
   });
 }
 
-async function exportHistoryCSV() {
+// Nothing once final or when the tip was not known
+function confirmationBadge(o) {
+  if (o.reorged) {
+    return '<span class="history-reorg" title="Block ' + o.reorged.height + ' was replaced">reorged ; awaiting confirmation</span>';
+  }
+  if (o.confirmations === undefined || o.is_final) return '';
+  return '<span class="history-confs">' + o.confirmations + '/' + o.final_after + ' conf</span>';
+}
+
+function startHistoryLabelEdit(row, entry) {
+  if (row.querySelector('.history-label-edit')) return;
+  var editor = document.createElement('div');
+  editor.className = 'history-label-edit';
+  editor.innerHTML =
+    '<input type="text" class="ab-edit-input" placeholder="Label" value="' + escapeHtml(entry.label || '') + '">' +
+    '<input type="text" class="ab-edit-input" placeholder="Note" value="' + escapeHtml(entry.note || '') + '">' +
+    '<button class="ab-save-btn">Save</button>' +
+    '<button class="ab-cancel-btn">Cancel</button>';
+  editor.addEventListener('click', function (ev) { ev.stopPropagation(); });
+  row.appendChild(editor);
+  var inputs = editor.querySelectorAll('input');
+  inputs[0].focus();
+  async function commit() {
+    try {
+      await invoke('set_output_label', {
+        txid: entry.txid,
+        outputIndex: entry.output_index,
+        label: inputs[0].value,
+        note: inputs[1].value,
+      });
+    } catch (e) {
+      console.error('Failed to save label:', e);
+    }
+    loadHistory();
+  }
+  editor.querySelector('.ab-save-btn').addEventListener('click', commit);
+  editor.querySelector('.ab-cancel-btn').addEventListener('click', function () { editor.remove(); });
+  inputs.forEach(function (el) {
+    el.addEventListener('keydown', function (ev) {
+      if (ev.key === 'Enter') commit();
+      if (ev.key === 'Escape') editor.remove();
+    });
+  });
+}
+
+// Null exports everything; empty bounds are open-ended
+function exportFilter() {
+  var range = document.getElementById('export-range').value;
+  var val = function (id) { return document.getElementById(id).value.trim(); };
+  if (range === 'date') {
+    return { from_date: val('export-from-date') || null, to_date: val('export-to-date') || null };
+  }
+  if (range === 'height') {
+    var height = function (id) { return val(id) === '' ? null : parseInt(val(id), 10); };
+    return { from_height: height('export-from-height'), to_height: height('export-to-height') };
+  }
+  return null;
+}
+
+function showExportRange() {
+  var range = document.getElementById('export-range').value;
+  document.querySelectorAll('.export-bound').forEach(function (el) {
+    var kind = el.id.indexOf('date') !== -1 ? 'date' : 'height';
+    el.style.display = kind === range ? 'inline-block' : 'none';
+  });
+}
+
+async function exportHistory() {
   var btn = document.getElementById('export-csv-btn');
   if (btn.dataset.openPath) {
     invoke('open_file', { path: btn.dataset.openPath });
     return;
   }
+  var format = document.getElementById('export-format').value;
   btn.disabled = true;
+  btn.title = '';
   btn.textContent = 'Exporting...';
   try {
-    var data = await api('/api/wallet/history');
-    var hasOutputsArray = data && Array.isArray(data.outputs);
-    var outputs = hasOutputsArray ? data.outputs : null;
-    if (!hasOutputsArray) {
-      try { outputs = JSON.parse(localStorage.getItem(walletKey('txCache')) || 'null'); } catch (_) {}
-    }
-    if (!outputs || outputs.length === 0) {
-      btn.textContent = 'No data';
-      setTimeout(function () { btn.disabled = false; btn.textContent = 'Export CSV'; }, 2000);
+    var savedPath = await invoke('export_history', { format: format, filter: exportFilter() });
+    if (!savedPath) {
+      btn.disabled = false;
+      btn.textContent = 'Export';
       return;
     }
-    var sorted = outputs.slice().sort(function (a, b) { return b.block_height - a.block_height; });
-    var lines = ['txid,output_index,amount_bnt,block_height,type,spent,spent_height'];
-    for (var i = 0; i < sorted.length; i++) {
-      var o = sorted[i];
-      var type = o.is_coinbase ? 'mining_reward' : (o.spent ? 'sent' : 'received');
-      lines.push(
-        o.txid + ',' +
-        o.output_index + ',' +
-        formatBNT(o.amount) + ',' +
-        o.block_height + ',' +
-        type + ',' +
-        o.spent + ',' +
-        (o.spent_height || '')
-      );
-    }
-    var csv = lines.join('\n');
-    var now = new Date();
-    var ts = now.getFullYear().toString() +
-      String(now.getMonth() + 1).padStart(2, '0') +
-      String(now.getDate()).padStart(2, '0') +
-      String(now.getHours()).padStart(2, '0') +
-      String(now.getMinutes()).padStart(2, '0') +
-      String(now.getSeconds()).padStart(2, '0');
-    var savedPath = await invoke('save_file', {
-      filename: 'blocknet-history-' + ts + '.csv',
-      contents: csv,
-    });
     btn.textContent = 'Saved to ' + savedPath;
     btn.disabled = false;
     btn.dataset.openPath = savedPath;
     setTimeout(function () {
-      btn.textContent = 'Export CSV';
+      btn.textContent = 'Export';
       delete btn.dataset.openPath;
     }, 5000);
   } catch (e) {
-    console.error('CSV export error:', e);
+    console.error('History export error:', e);
     btn.textContent = 'Export failed';
-    setTimeout(function () { btn.disabled = false; btn.textContent = 'Export CSV'; }, 3000);
+    btn.title = normalizeError(e);
+    setTimeout(function () { btn.disabled = false; btn.textContent = 'Export'; }, 3000);
+  }
+}
+
+async function saveDiagnostics() {
+  var btn = document.getElementById('diagnostics-btn');
+  if (btn.dataset.openPath) {
+    invoke('open_file', { path: btn.dataset.openPath });
+    return;
+  }
+  btn.disabled = true;
+  btn.textContent = 'Collecting...';
+  try {
+    var savedPath = await invoke('create_diagnostics_bundle');
+    btn.disabled = false;
+    if (!savedPath) {
+      btn.textContent = 'Save Bundle';
+      return;
+    }
+    btn.textContent = 'Saved to ' + savedPath;
+    btn.dataset.openPath = savedPath;
+    setTimeout(function () {
+      btn.textContent = 'Save Bundle';
+      delete btn.dataset.openPath;
+    }, 5000);
+  } catch (e) {
+    console.error('Diagnostics bundle error:', e);
+    btn.textContent = 'Failed';
+    setTimeout(function () { btn.disabled = false; btn.textContent = 'Save Bundle'; }, 3000);
   }
 }
 
@@ -577,7 +690,9 @@ async function loadMining() {
     btn.className = 'mining-toggle-btn' + (data.running ? ' running' : '');
   }
 
+  await refreshScheduleNote();
   await refreshMiningDifficultySparkline();
+  await loadMiningEarnings();
   await loadMiningMempool();
 }
 
@@ -732,8 +847,78 @@ async function loadMiningMempool() {
     '</div>';
 }
 
+var MINING_EARNINGS_REFRESH_MS = 60000;
+var miningEarningsLastRefresh = 0;
+
+async function loadMiningEarnings() {
+  var listEl = document.getElementById('mining-earnings-list');
+  var metaEl = document.getElementById('mining-earnings-meta');
+  var now = Date.now();
+  if (now - miningEarningsLastRefresh < MINING_EARNINGS_REFRESH_MS) return;
+  miningEarningsLastRefresh = now;
+
+  var stats;
+  try {
+    stats = await invoke('get_mining_stats', { days: 7 });
+  } catch (e) {
+    metaEl.textContent = 'Unavailable';
+    listEl.innerHTML = '<div class="empty">Unlock the wallet to see earnings</div>';
+    miningEarningsLastRefresh = 0;
+    return;
+  }
+
+  metaEl.textContent = stats.total_earned_bnt + ' BNT in 7 days';
+  var days = stats.days.slice().reverse();
+  var html = days.map(function (d) {
+    var detail = d.blocks + (d.blocks === 1 ? ' block' : ' blocks');
+    if (d.avg_hashrate > 0) detail += ' ; ' + d.avg_hashrate.toFixed(2) + ' H/s avg';
+    return '<div class="mempool-next-block' + (d.earned === 0 ? ' zero' : '') + '">' +
+      '<span class="label">' + escapeHtml(d.date) + ' ; ' + detail + '</span>' +
+      '<span class="value">' + escapeHtml(d.earned_bnt) + ' BNT</span>' +
+    '</div>';
+  }).join('');
+  if (stats.unmatched.length) {
+    html += '<div class="mempool-next-block zero">' +
+      '<span class="label">Found but not paid to this wallet (orphaned or not yet scanned)</span>' +
+      '<span class="value">' + stats.unmatched.length + '</span>' +
+    '</div>';
+  }
+  listEl.innerHTML = html || '<div class="empty">No mining activity recorded yet</div>';
+}
+
+async function exportMiningStats() {
+  var btn = document.getElementById('mining-export-btn');
+  if (btn.dataset.openPath) {
+    invoke('open_file', { path: btn.dataset.openPath });
+    return;
+  }
+  btn.disabled = true;
+  btn.textContent = 'Exporting...';
+  try {
+    var savedPath = await invoke('export_mining_stats', { days: null });
+    btn.disabled = false;
+    if (!savedPath) {
+      btn.textContent = 'Export CSV';
+      return;
+    }
+    btn.textContent = 'Saved';
+    btn.title = savedPath;
+    btn.dataset.openPath = savedPath;
+    setTimeout(function () {
+      btn.textContent = 'Export CSV';
+      btn.title = '';
+      delete btn.dataset.openPath;
+    }, 5000);
+  } catch (e) {
+    console.error('Mining export error:', e);
+    btn.textContent = 'Export failed';
+    setTimeout(function () { btn.disabled = false; btn.textContent = 'Export CSV'; }, 3000);
+  }
+}
+
 async function toggleMining() {
   const btn = document.getElementById('mining-toggle');
+  document.getElementById('mining-restore-note').style.display = 'none';
   btn.disabled = true;
   btn.textContent = isMining ? 'Stopping...' : 'Starting...';
   try {
@@ -786,29 +971,45 @@ function changeThreads(delta) {
 
 // --- Network ---
 
+function peerButtons(buttons) {
+  return '<div class="ab-actions">' + buttons.map(function (b) {
+    return '<button class="' + b[0] + '" data-action="' + b[1] + '">' + b[2] + '</button>';
+  }).join('') + '</div>';
+}
+
 async function loadNetwork() {
-  const [peers, banned] = await Promise.all([
-    api('/api/peers'),
-    api('/api/peers/banned'),
-  ]);
+  const overview = await invoke('get_peer_overview');
 
-  document.getElementById('network-peer-count').textContent = peers.count;
+  document.getElementById('network-peer-count').textContent = overview.connected.length;
   const peerList = document.getElementById('network-peers');
-  if (peers.peers && peers.peers.length > 0) {
-    peerList.innerHTML = peers.peers.map(p =>
-      '<div class="peer-row">' + p + '</div>'
+  if (overview.connected.length > 0) {
+    peerList.innerHTML = overview.connected.map(p =>
+      '<div class="peer-row">' + escapeHtml(p) + '</div>'
     ).join('');
   } else {
     peerList.innerHTML = '<div class="empty">No peers connected</div>';
   }
 
-  document.getElementById('network-banned-count').textContent = banned.count;
+  document.getElementById('network-preferred-count').textContent = overview.preferred.length;
+  const preferredList = document.getElementById('network-preferred');
+  if (overview.preferred.length > 0) {
+    preferredList.innerHTML = overview.preferred.map(p =>
+      '<div class="peer-row managed" data-addr="' + escapeHtml(p.multiaddr) + '">' +
+        '<span>' + (p.label ? escapeHtml(p.label) + ' <span class="d">' + escapeHtml(p.multiaddr) + '</span>' : escapeHtml(p.multiaddr)) + '</span>' +
+        peerButtons([['ab-del-btn', 'remove', 'Remove']]) +
+      '</div>'
+    ).join('');
+  } else {
+    preferredList.innerHTML = '<div class="empty">No preferred peers</div>';
+  }
+
+  document.getElementById('network-banned-count').textContent = overview.banned.length;
   const bannedList = document.getElementById('network-banned');
-  if (banned.banned && banned.banned.length > 0) {
-    bannedList.innerHTML = banned.banned.map(b =>
+  if (overview.banned.length > 0) {
+    bannedList.innerHTML = overview.banned.map(b =>
       '<div class="peer-row banned">' +
-        '<span>' + b.peer_id.substring(0, 24) + '...</span>' +
-        '<span class="d">' + b.reason + '</span>' +
+        '<span>' + escapeHtml(b.peer_id.substring(0, 24)) + '...</span>' +
+        '<span class="d">' + escapeHtml(b.reason) + (b.permanent ? ' ; permanent' : '') + '</span>' +
       '</div>'
     ).join('');
   } else {
@@ -816,45 +1017,268 @@ async function loadNetwork() {
   }
 }
 
+function showPeerStatus(msg, type) {
+  var el = document.getElementById('peer-status');
+  el.textContent = msg;
+  el.className = 'status-message ' + type;
+  el.style.display = msg ? '' : 'none';
+}
+
+async function addPeer() {
+  var input = document.getElementById('peer-multiaddr');
+  var label = document.getElementById('peer-label');
+  if (!input.value.trim()) return;
+  try {
+    await invoke('add_preferred_peer', { multiaddr: input.value, label: label.value || null });
+    input.value = '';
+    label.value = '';
+    showPeerStatus('Saved ; the node connects to it the next time it starts.', 'success');
+  } catch (e) {
+    showPeerStatus(normalizeError(e), 'error');
+  }
+  await loadNetwork().catch(function () {});
+}
+
+async function onPeerAction(ev) {
+  var btn = ev.target.closest('button[data-action]');
+  if (!btn) return;
+  var row = btn.closest('.peer-row');
+  var action = btn.dataset.action;
+  btn.disabled = true;
+  try {
+    if (action === 'remove') {
+      await invoke('remove_preferred_peer', { multiaddr: row.dataset.addr });
+      showPeerStatus('Removed ; takes effect the next time the node starts.', 'success');
+    }
+  } catch (e) {
+    showPeerStatus(normalizeError(e), 'error');
+  }
+  btn.disabled = false;
+  await loadNetwork().catch(function () {});
+}
+
+document.getElementById('peer-prefer-btn').addEventListener('click', addPeer);
+document.getElementById('network-preferred').addEventListener('click', onPeerAction);
+
+// --- Explorer ---
+
+// What the explorer view shows next; null means the latest block
+var explorerTarget = null;
+
+function showExplorer(kind, id) {
+  explorerTarget = { kind: kind, id: String(id) };
+  if (currentView === 'explorer') {
+    loadExplorer();
+  } else {
+    navigate('explorer');
+  }
+}
+
+function showExplorerStatus(msg, type) {
+  var el = document.getElementById('explorer-status');
+  el.textContent = msg;
+  el.className = 'status-message ' + type;
+  el.style.display = msg ? '' : 'none';
+}
+
+function explorerLink(kind, id, text) {
+  return '<button class="text-link explorer-link" data-kind="' + kind + '" data-id="' + escapeHtml(String(id)) + '">' +
+    escapeHtml(text || String(id)) + '</button>';
+}
+
+function explorerStat(label, value) {
+  return '<div class="mempool-stat-item"><span class="label">' + label + '</span><span class="value">' + value + '</span></div>';
+}
+
+function explorerTime(ts) {
+  return ts ? escapeHtml(new Date(ts * 1000).toLocaleString()) : '--';
+}
+
+function confirmationLabel(n) {
+  return n === 0 ? 'Unconfirmed' : n.toLocaleString() + (n === 1 ? ' confirmation' : ' confirmations');
+}
+
+function renderExplorerBlock(b) {
+  var prev = b.height > 0 ? explorerLink('block', b.height - 1, '← ' + (b.height - 1).toLocaleString()) : '';
+  var next = b.height < b.tip_height ? explorerLink('block', b.height + 1, (b.height + 1).toLocaleString() + ' →') : '';
+  var txs = b.transactions.map(function (t) {
+    return '<div class="mempool-next-block explorer-tx-row">' +
+      explorerLink('tx', t.hash, t.hash.substring(0, 16) + '...') +
+      '<span class="d">' + (t.is_coinbase ? 'coinbase' : t.inputs + ' in ; ' + t.outputs + ' out ; fee ' + formatBNT(t.fee) + ' BNT') + '</span>' +
+    '</div>';
+  }).join('');
+  return '<div class="mempool-panel">' +
+    '<div class="mempool-header-row"><h2>Block ' + b.height.toLocaleString() + '</h2>' +
+      '<span class="mempool-meta">' + confirmationLabel(b.confirmations) + '</span></div>' +
+    '<div class="explorer-nav">' + prev + next + '</div>' +
+    '<div class="explorer-hash">' + escapeHtml(b.hash) + '</div>' +
+    '<div class="mempool-stats-grid">' +
+      explorerStat('Time', explorerTime(b.timestamp)) +
+      explorerStat('Transactions', b.tx_count.toLocaleString()) +
+      explorerStat('Reward', formatBNT(b.reward) + ' BNT') +
+      explorerStat('Difficulty', b.difficulty.toLocaleString()) +
+      explorerStat('Previous', b.height > 0 ? explorerLink('block', b.prev_hash, b.prev_hash.substring(0, 16) + '...') : '--') +
+      explorerStat('Nonce', String(b.nonce)) +
+    '</div>' +
+  '</div>' +
+  '<div class="mempool-panel">' +
+    '<div class="mempool-header-row"><h2>Transactions</h2></div>' +
+    '<div class="mempool-list">' + (txs || '<div class="empty">No transactions</div>') + '</div>' +
+  '</div>';
+}
+
+function renderExplorerTx(t) {
+  var inputs = Array.isArray(t.tx.inputs) ? t.tx.inputs : [];
+  var outputs = Array.isArray(t.tx.outputs) ? t.tx.outputs : [];
+  var rows = inputs.map(function (i) {
+    return '<div class="mempool-next-block explorer-tx-row"><span class="label">Input</span>' +
+      '<span class="d">' + escapeHtml(String(i.key_image || '').substring(0, 16)) + '... ; ring ' + (i.ring_size || 0) + '</span></div>';
+  }).concat(outputs.map(function (o) {
+    return '<div class="mempool-next-block explorer-tx-row"><span class="label">Output</span>' +
+      '<span class="d">' + escapeHtml(String(o.commitment || '').substring(0, 16)) + '...</span></div>';
+  })).join('');
+  return '<div class="mempool-panel">' +
+    '<div class="mempool-header-row"><h2>Transaction</h2>' +
+      '<span class="mempool-meta">' + (t.in_mempool ? 'In mempool' : confirmationLabel(t.confirmations)) + '</span></div>' +
+    '<div class="explorer-hash">' + escapeHtml(t.hash) + '</div>' +
+    '<div class="mempool-stats-grid">' +
+      explorerStat('Block', t.block_height != null ? explorerLink('block', t.block_hash || t.block_height, t.block_height.toLocaleString()) : '--') +
+      explorerStat('Time', explorerTime(t.timestamp)) +
+      explorerStat('Fee', formatBNT(t.fee) + ' BNT') +
+      explorerStat('Inputs / Outputs', inputs.length + ' / ' + outputs.length) +
+    '</div>' +
+    '<div class="explorer-nav"><button class="text-link" id="explorer-public-btn">Open on public explorer</button></div>' +
+  '</div>' +
+  '<div class="mempool-panel">' +
+    '<div class="mempool-header-row"><h2>Inputs and Outputs</h2></div>' +
+    '<div class="mempool-list">' + (rows || '<div class="empty">No details</div>') + '</div>' +
+  '</div>';
+}
+
+function renderExplorerMempool(m) {
+  return '<div class="mempool-panel">' +
+    '<div class="mempool-header-row"><h2>Mempool</h2>' +
+      '<span class="mempool-meta">' + m.count.toLocaleString() + ' tx • ' + formatBytes(m.size_bytes) + '</span></div>' +
+    '<div class="mempool-stats-grid">' +
+      explorerStat('Min Fee', formatBNT(m.min_fee) + ' BNT') +
+      explorerStat('Avg Fee', formatBNT(Math.round(m.avg_fee)) + ' BNT') +
+      explorerStat('Max Fee', formatBNT(m.max_fee) + ' BNT') +
+      explorerStat('Next Block', (m.tip_height + 1).toLocaleString()) +
+    '</div>' +
+  '</div>';
+}
+
+async function loadExplorer() {
+  var page = document.getElementById('explorer-page');
+  var target = explorerTarget;
+  showExplorerStatus('', 'success');
+  try {
+    if (target && target.kind === 'tx') {
+      var tx = await invoke('explorer_tx', { hash: target.id });
+      page.innerHTML = renderExplorerTx(tx);
+      document.getElementById('explorer-public-btn').addEventListener('click', function () {
+        invoke('open_explorer', { kind: 'tx', id: tx.hash }).catch(function (e) {
+          showExplorerStatus(normalizeError(e), 'error');
+        });
+      });
+    } else if (target) {
+      page.innerHTML = renderExplorerBlock(await invoke('explorer_block', { id: target.id }));
+    } else {
+      var mempool = await invoke('explorer_mempool');
+      var tip = await invoke('explorer_block', { id: String(mempool.tip_height) });
+      page.innerHTML = renderExplorerMempool(mempool) + renderExplorerBlock(tip);
+    }
+  } catch (e) {
+    page.innerHTML = '';
+    showExplorerStatus(normalizeError(e), 'error');
+  }
+}
+
+async function searchExplorer() {
+  var input = document.getElementById('explorer-query');
+  if (!input.value.trim()) return;
+  try {
+    var hit = await invoke('explorer_search', { query: input.value });
+    input.value = '';
+    showExplorer(hit.kind, hit.id);
+  } catch (e) {
+    showExplorerStatus(normalizeError(e), 'error');
+  }
+}
+
+document.getElementById('explorer-search-btn').addEventListener('click', searchExplorer);
+document.getElementById('explorer-query').addEventListener('keydown', function (e) {
+  if (e.key === 'Enter') searchExplorer();
+});
+document.getElementById('explorer-tip-btn').addEventListener('click', function () {
+  explorerTarget = null;
+  loadExplorer();
+});
+document.getElementById('explorer-page').addEventListener('click', function (ev) {
+  var link = ev.target.closest('.explorer-link');
+  if (link) showExplorer(link.dataset.kind, link.dataset.id);
+});
+
 // --- Address Book ---
+// Contacts live in the backend, per wallet; see address_book.rs
+
+var addressBookCache = [];
 
-function getAddressBook() {
+async function migrateAddressBook() {
+  var raw = null;
+  try { raw = localStorage.getItem(walletKey('addressBook')); } catch (_) {}
+  if (!raw) return;
   try {
-    return JSON.parse(localStorage.getItem(walletKey('addressBook')) || '[]');
-  } catch (_) {
-    return [];
+    await invoke('migrate_contacts', { json: raw });
+    localStorage.removeItem(walletKey('addressBook'));
+  } catch (e) {
+    console.error('Address book migration failed:', e);
   }
 }
 
-function saveAddressBook(book) {
-  localStorage.setItem(walletKey('addressBook'), JSON.stringify(book));
+async function getAddressBook(query) {
+  try {
+    addressBookCache = await invoke('list_contacts', { query: query || null });
+  } catch (e) {
+    console.error('Failed to load contacts:', e);
+    addressBookCache = [];
+  }
+  return addressBookCache;
 }
 
-function renderAddressBook() {
+async function renderAddressBook() {
   var list = document.getElementById('address-book-list');
   if (!list) return;
-  var book = getAddressBook();
+  await migrateAddressBook();
+  var book = await getAddressBook();
   if (book.length === 0) {
     list.innerHTML = '<div class="empty">No saved contacts</div>';
     return;
   }
-  list.innerHTML = book.map(function (entry, i) {
-    return '<div class="address-book-row" data-idx="' + i + '">' +
+  list.innerHTML = book.map(function (entry) {
+    return '<div class="address-book-row" data-id="' + escapeHtml(entry.id) + '">' +
       '<div class="ab-info">' +
-        '<span class="ab-name">' + escapeHtml(entry.name) + '</span>' +
-        '<span class="ab-addr d">' + entry.address.substring(0, 24) + '...</span>' +
+        '<span class="ab-name">' + escapeHtml(entry.label) + '</span>' +
+        '<span class="ab-addr d">' + escapeHtml(entry.address.substring(0, 24)) + '...</span>' +
+        (entry.notes ? '<span class="ab-notes d">' + escapeHtml(entry.notes) + '</span>' : '') +
       '</div>' +
       '<div class="ab-actions">' +
-        '<button class="ab-use-btn" data-idx="' + i + '">Use</button>' +
-        '<button class="ab-edit-btn" data-idx="' + i + '">Edit</button>' +
-        '<button class="ab-del-btn" data-idx="' + i + '">Del</button>' +
+        '<button class="ab-use-btn">Use</button>' +
+        '<button class="ab-edit-btn">Edit</button>' +
+        '<button class="ab-del-btn">Del</button>' +
       '</div>' +
     '</div>';
   }).join('');
 
+  function entryFor(btn) {
+    var row = btn.closest('.address-book-row');
+    if (!row) return null;
+    return addressBookCache.find(function (e) { return e.id === row.dataset.id; }) || null;
+  }
+
   list.querySelectorAll('.ab-use-btn').forEach(function (btn) {
     btn.addEventListener('click', function () {
-      var entry = getAddressBook()[parseInt(btn.dataset.idx)];
+      var entry = entryFor(btn);
       if (entry) {
         document.getElementById('send-address').value = entry.address;
         hideAddressSuggestions();
@@ -864,47 +1288,57 @@ function renderAddressBook() {
 
   list.querySelectorAll('.ab-edit-btn').forEach(function (btn) {
     btn.addEventListener('click', function () {
-      var idx = parseInt(btn.dataset.idx);
       var row = btn.closest('.address-book-row');
-      if (!row) return;
-      var book = getAddressBook();
-      var entry = book[idx];
-      if (!entry) return;
+      var entry = entryFor(btn);
+      if (!row || !entry) return;
       var info = row.querySelector('.ab-info');
       var actions = row.querySelector('.ab-actions');
-      info.innerHTML = '<input type="text" class="ab-edit-input" value="' + escapeHtml(entry.name) + '">';
+      info.innerHTML =
+        '<input type="text" class="ab-edit-input" value="' + escapeHtml(entry.label) + '">' +
+        '<input type="text" class="ab-edit-input ab-edit-notes" placeholder="Notes" value="' + escapeHtml(entry.notes || '') + '">';
       actions.innerHTML =
         '<button class="ab-save-btn">Save</button>' +
         '<button class="ab-cancel-btn">Cancel</button>';
       var input = info.querySelector('.ab-edit-input');
+      var notesInput = info.querySelector('.ab-edit-notes');
       input.focus();
       input.select();
-      function commitEdit() {
+      async function commitEdit() {
         var val = input.value.trim();
         if (val) {
-          var fresh = getAddressBook();
-          if (fresh[idx]) {
-            fresh[idx].name = val;
-            saveAddressBook(fresh);
+          try {
+            await invoke('update_contact', {
+              id: entry.id,
+              label: val,
+              address: entry.address,
+              notes: notesInput.value,
+            });
+          } catch (e) {
+            showSendStatus(normalizeError(e), 'error');
           }
         }
         renderAddressBook();
       }
       actions.querySelector('.ab-save-btn').addEventListener('click', commitEdit);
       actions.querySelector('.ab-cancel-btn').addEventListener('click', function () { renderAddressBook(); });
-      input.addEventListener('keydown', function (ev) {
-        if (ev.key === 'Enter') commitEdit();
-        if (ev.key === 'Escape') renderAddressBook();
+      [input, notesInput].forEach(function (el) {
+        el.addEventListener('keydown', function (ev) {
+          if (ev.key === 'Enter') commitEdit();
+          if (ev.key === 'Escape') renderAddressBook();
+        });
       });
     });
   });
 
   list.querySelectorAll('.ab-del-btn').forEach(function (btn) {
-    btn.addEventListener('click', function () {
-      var idx = parseInt(btn.dataset.idx);
-      var book = getAddressBook();
-      book.splice(idx, 1);
-      saveAddressBook(book);
+    btn.addEventListener('click', async function () {
+      var entry = entryFor(btn);
+      if (!entry) return;
+      try {
+        await invoke('delete_contact', { id: entry.id });
+      } catch (e) {
+        showSendStatus(normalizeError(e), 'error');
+      }
       renderAddressBook();
     });
   });
@@ -941,7 +1375,7 @@ function handleSaveContact() {
   }
 }
 
-function commitSaveContact() {
+async function commitSaveContact() {
   var address = document.getElementById('send-address').value.trim();
   var inp = document.getElementById('save-contact-name');
   if (!inp) return;
@@ -950,14 +1384,17 @@ function commitSaveContact() {
     inp.focus();
     return;
   }
-  var book = getAddressBook();
-  var existing = book.findIndex(function (e) { return e.address === address; });
-  if (existing >= 0) {
-    book[existing].name = name;
-  } else {
-    book.push({ name: name, address: address });
+  var existing = addressBookCache.find(function (e) { return e.address === address; });
+  try {
+    if (existing) {
+      await invoke('update_contact', { id: existing.id, label: name, address: address, notes: null });
+    } else {
+      await invoke('add_contact', { label: name, address: address, notes: null });
+    }
+  } catch (e) {
+    showSendStatus(normalizeError(e), 'error');
+    return;
   }
-  saveAddressBook(book);
   dismissSaveContactInline();
   renderAddressBook();
   showSendStatus('Contact saved', 'success');
@@ -970,22 +1407,12 @@ function dismissSaveContactInline() {
   if (saveBtn) saveBtn.style.display = '';
 }
 
-function showAddressSuggestions() {
+async function showAddressSuggestions() {
   var input = document.getElementById('send-address');
   var container = document.getElementById('address-book-suggestions');
-  var query = (input.value || '').trim().toLowerCase();
-  var book = getAddressBook();
-
-  if (!query || book.length === 0) {
-    container.innerHTML = '';
-    container.style.display = 'none';
-    return;
-  }
-
-  var matches = book.filter(function (e) {
-    return e.name.toLowerCase().indexOf(query) >= 0 || e.address.toLowerCase().indexOf(query) >= 0;
-  });
+  var query = (input.value || '').trim();
 
+  var matches = query ? await getAddressBook(query) : [];
   if (matches.length === 0) {
     container.innerHTML = '';
     container.style.display = 'none';
@@ -994,9 +1421,9 @@ function showAddressSuggestions() {
 
   container.style.display = 'block';
   container.innerHTML = matches.map(function (e) {
-    return '<div class="address-suggestion" data-address="' + e.address + '">' +
-      '<span class="as-name">' + escapeHtml(e.name) + '</span>' +
-      '<span class="as-addr d">' + e.address.substring(0, 20) + '...</span>' +
+    return '<div class="address-suggestion" data-address="' + escapeHtml(e.address) + '">' +
+      '<span class="as-name">' + escapeHtml(e.label) + '</span>' +
+      '<span class="as-addr d">' + escapeHtml(e.address.substring(0, 20)) + '...</span>' +
     '</div>';
   }).join('');
 
@@ -1017,6 +1444,29 @@ function hideAddressSuggestions() {
   }
 }
 
+async function exportContacts(format) {
+  try {
+    var path = await invoke('export_contacts', { format: format });
+    showSendStatus('Contacts saved to ' + path, 'success');
+  } catch (e) {
+    showSendStatus(normalizeError(e), 'error');
+  }
+}
+
+async function importContacts() {
+  try {
+    var summary = await invoke('import_contacts');
+    var msg = 'Imported ' + summary.added + ' contact' + (summary.added === 1 ? '' : 's');
+    if (summary.skipped) msg += ', ' + summary.skipped + ' already saved';
+    if (summary.invalid.length) msg += ', ' + summary.invalid.length + ' invalid';
+    showSendStatus(msg, summary.invalid.length ? 'info' : 'success');
+    renderAddressBook();
+  } catch (e) {
+    var msg2 = normalizeError(e);
+    if (msg2 !== 'No file selected' && msg2 !== 'Dialog cancelled') showSendStatus(msg2, 'error');
+  }
+}
+
 function escapeHtml(str) {
   return str.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;').replace(/"/g, '&quot;');
 }
@@ -1065,13 +1515,14 @@ async function handleSend(e) {
   showSendStatus('Building transaction...', 'info');
 
   try {
-    const result = await api('/api/wallet/send', {
-      method: 'POST',
-      body: { address, amount },
+    const memo = document.getElementById('send-memo').value.trim();
+    const result = await withReauth(function () {
+      return invoke('send_funds', { address, amount, memo: memo || null });
     });
     showSendStatus('Sent! TX: ' + result.txid.substring(0, 24) + '... Fee: ' + formatBNT(result.fee) + ' BNT', 'success');
     document.getElementById('send-address').value = '';
     document.getElementById('send-amount').value = '';
+    document.getElementById('send-memo').value = '';
   } catch (e) {
     showSendStatus(normalizeError(e), 'error');
   } finally {
@@ -1199,7 +1650,7 @@ function handleDeleteWallet(name) {
     '<button class="ab-cancel-btn">No</button>';
 
   actionsEl.querySelector('.wallet-del-yes').addEventListener('click', function () {
-    invoke('delete_wallet', { name: name })
+    withReauth(function () { return invoke('delete_wallet', { name: name }); })
       .then(function () { loadWalletList(); })
       .catch(function (e) { showSettingsStatus(normalizeError(e), 'error'); loadWalletList(); });
   });
@@ -1257,9 +1708,9 @@ async function handleImportSeed() {
     showImportStatus('Enter your 12-word recovery phrase', 'error');
     return;
   }
-  var words = mnemonic.split(/\s+/);
-  if (words.length !== 12) {
-    showImportStatus('Recovery phrase must be exactly 12 words', 'error');
+  var check = await invoke('check_mnemonic', { phrase: mnemonic });
+  if (!check.valid) {
+    showImportStatus(check.error, 'error');
     return;
   }
   if (password.length < 3) {
@@ -1272,21 +1723,12 @@ async function handleImportSeed() {
 
   btn.disabled = true;
   btn.textContent = 'Importing...';
-  showImportStatus('Stopping daemon...', 'info');
 
   try {
-    // Need a fresh daemon with no wallet loaded
+    // The backend restarts the daemon if a wallet is still loaded
     stopPolling();
-    await invoke('stop_daemon');
-    await new Promise(function (r) { setTimeout(r, 500); });
-
-    showImportStatus('Starting daemon...', 'info');
-    await ensureDaemonReady();
-
     showImportStatus('Importing wallet from seed...', 'info');
-    var body = { mnemonic: words.join(' '), password: password };
-    if (filename) body.filename = filename;
-    var result = await api('/api/wallet/import', { method: 'POST', body: body });
+    var result = await invoke('import_seed', { mnemonic: mnemonic, password: password, filename: filename || null });
 
     // Update active wallet to the imported file
     var importedName = result.filename || filename || 'wallet.dat';
@@ -1326,8 +1768,8 @@ async function handlePasswordScreenImportSubmit() {
   }
 
   if (!mnemonic) { showPsStatus('Enter your 12-word recovery phrase', 'error'); return; }
-  var words = mnemonic.split(/\s+/);
-  if (words.length !== 12) { showPsStatus('Recovery phrase must be exactly 12 words', 'error'); return; }
+  var check = await invoke('check_mnemonic', { phrase: mnemonic });
+  if (!check.valid) { showPsStatus(check.error, 'error'); return; }
   if (password.length < 3) { showPsStatus('Password must be at least 3 characters', 'error'); return; }
   if (filename && !filename.endsWith('.dat')) filename = filename + '.dat';
 
@@ -1339,9 +1781,7 @@ async function handlePasswordScreenImportSubmit() {
     await ensureDaemonReady();
 
     showPsStatus('Importing wallet from seed...', 'info');
-    var body = { mnemonic: words.join(' '), password: password };
-    if (filename) body.filename = filename;
-    var result = await api('/api/wallet/import', { method: 'POST', body: body });
+    var result = await invoke('import_seed', { mnemonic: mnemonic, password: password, filename: filename || null });
 
     var importedName = result.filename || filename || 'wallet.dat';
     // The daemon already loaded this wallet via import, just set it active
@@ -1412,10 +1852,11 @@ async function handleViewSeed() {
   }
 
   try {
+    const token = await invoke('request_confirmation', { action: 'reveal-seed' });
+    if (!token) return;
     showSeedStatus('Loading seed...', 'info');
-    const data = await api('/api/wallet/seed', {
-      method: 'POST',
-      body: { password: sessionPassword },
+    const data = await withReauth(function () {
+      return invoke('reveal_seed', { password: sessionPassword, token: token });
     });
     document.getElementById('seed-status').style.display = 'none';
     const el = document.getElementById('seed-display');
@@ -1465,7 +1906,7 @@ async function handleResetChainData() {
     document.getElementById('dash-syncing').textContent = 'Resyncing';
     dashLastHeight = -1;
     dashLastTxCount = -1;
-    await invoke('reset_blockchain_data');
+    await withReauth(function () { return invoke('reset_blockchain_data'); });
     showSettingsStatus('Starting daemon...', 'info');
     await ensureDaemonReady();
     if (sessionPassword) {
@@ -1689,10 +2130,12 @@ async function showApp() {
   const app = document.getElementById('app');
   if (passwordScreen) passwordScreen.style.display = 'none';
   app.style.display = 'flex';
+  await paintCachedBalance();
   navigate('dashboard');
   startPolling();
   playTada();
   invoke('set_tray_unlocked', { unlocked: true }).catch(function() {});
+  if (pendingPaymentRequest) applyPaymentRequest(pendingPaymentRequest);
 }
 
 async function showAppFromSplash() {
@@ -1703,9 +2146,11 @@ async function showAppFromSplash() {
   splash.classList.add('fade-out');
   app.style.display = 'flex';
   setTimeout(() => splash.remove(), 1000);
+  await paintCachedBalance();
   navigate('dashboard');
   startPolling();
   invoke('set_tray_unlocked', { unlocked: true }).catch(function() {});
+  if (pendingPaymentRequest) applyPaymentRequest(pendingPaymentRequest);
 }
 
 function showStatus(message, type) {
@@ -1780,18 +2225,18 @@ async function handlePasswordSubmit(e) {
   }
 }
 
+// The backend waits stage by stage (cookie, listener, status) and reports
+// progress through daemon-stage events
 async function waitForDaemon() {
-  const maxAttempts = 60;
-  let attempts = 0;
-  while (attempts < maxAttempts) {
-    const ready = await invoke('check_daemon_ready');
-    if (ready) return;
-    await new Promise(r => setTimeout(r, 500));
-    attempts++;
-  }
-  throw new Error('Daemon failed to start within timeout');
+  await invoke('wait_for_daemon');
 }
 
+var DAEMON_STAGE_LABELS = {
+  cookie: 'Starting daemon...',
+  listener: 'Waiting for daemon API...',
+  status: 'Connecting to daemon...',
+};
+
 async function ensureDaemonReady() {
   const alreadyReady = await invoke('check_daemon_ready');
   if (alreadyReady) return;
@@ -1877,7 +2322,7 @@ function showSecurityBlockedModal() {
 
 async function init() {
   try {
-    if (!window.__TAURI__ || !window.__TAURI__.core) {
+    if (!tauri || !tauri.core) {
       throw new Error('Tauri API not available');
     }
 
@@ -1897,6 +2342,8 @@ async function init() {
     playIntro();
     await new Promise(r => setTimeout(r, 1000));
 
+    invoke('frontend_ready').catch(function () {});
+
     if (daemonReady) {
       try {
         // If wallet is already loaded/unlocked, go straight to app.
@@ -1932,13 +2379,27 @@ document.getElementById('copy-address').addEventListener('click', () => {
   const addr = document.getElementById('receive-address').textContent;
   navigator.clipboard.writeText(addr);
 });
+document.getElementById('receive-amount').addEventListener('input', renderReceiveQR);
+document.getElementById('copy-payment-link').addEventListener('click', async () => {
+  try {
+    navigator.clipboard.writeText(await receiveQRText());
+  } catch (e) {
+    console.error('Payment link error:', e);
+  }
+});
 document.getElementById('qr-container').addEventListener('click', showQROverlay);
 document.getElementById('qr-overlay').addEventListener('click', dismissQROverlay);
 document.getElementById('mining-toggle').addEventListener('click', toggleMining);
 document.getElementById('threads-inc').addEventListener('click', function () { changeThreads(1); });
 document.getElementById('threads-dec').addEventListener('click', function () { changeThreads(-1); });
-document.getElementById('export-csv-btn').addEventListener('click', exportHistoryCSV);
+document.getElementById('export-csv-btn').addEventListener('click', exportHistory);
+document.getElementById('export-range').addEventListener('change', showExportRange);
+document.getElementById('diagnostics-btn').addEventListener('click', saveDiagnostics);
+document.getElementById('mining-export-btn').addEventListener('click', exportMiningStats);
 document.getElementById('save-contact-btn').addEventListener('click', handleSaveContact);
+document.getElementById('import-contacts-btn').addEventListener('click', importContacts);
+document.getElementById('export-contacts-csv-btn').addEventListener('click', function () { exportContacts('csv'); });
+document.getElementById('export-contacts-json-btn').addEventListener('click', function () { exportContacts('json'); });
 document.getElementById('send-address').addEventListener('input', showAddressSuggestions);
 document.getElementById('send-address').addEventListener('focus', showAddressSuggestions);
 document.getElementById('send-address').addEventListener('blur', function () {
@@ -1993,6 +2454,692 @@ document.querySelectorAll('.nav-link').forEach(btn => {
   btn.addEventListener('click', () => navigate(btn.dataset.view));
 });
 
+// --- Auto-lock ---
+// The idle timer runs in the backend; the page only reports that the user is
+// still around and reacts when the backend locks the wallet.
+
+var lastActivityReport = 0;
+
+function reportActivity() {
+  var now = Date.now();
+  if (now - lastActivityReport < 15000) return;
+  lastActivityReport = now;
+  invoke('report_activity').catch(function () {});
+}
+
+['mousemove', 'mousedown', 'keydown', 'wheel', 'touchstart'].forEach(function (type) {
+  document.addEventListener(type, reportActivity, { passive: true });
+});
+
+async function loadAutoLockSettings() {
+  var s = await invoke('get_autolock_settings');
+  var timeout = document.getElementById('autolock-timeout');
+  var known = Array.prototype.some.call(timeout.options, function (o) { return o.value === String(s.idle_timeout_secs); });
+  if (!known) {
+    var opt = document.createElement('option');
+    opt.value = String(s.idle_timeout_secs);
+    opt.textContent = 'After ' + Math.round(s.idle_timeout_secs / 60) + ' minutes';
+    timeout.appendChild(opt);
+  }
+  timeout.value = String(s.idle_timeout_secs);
+  document.getElementById('autolock-hide').checked = s.lock_on_hide;
+  document.getElementById('autolock-suspend').checked = s.lock_on_suspend;
+  document.getElementById('autolock-screen').checked = s.lock_on_screen_lock;
+}
+
+// Loosening these needs the password; a cancelled prompt puts the form back
+function saveAutoLockSettings() {
+  var settings = {
+    idle_timeout_secs: parseInt(document.getElementById('autolock-timeout').value, 10) || 0,
+    lock_on_hide: document.getElementById('autolock-hide').checked,
+    lock_on_suspend: document.getElementById('autolock-suspend').checked,
+    lock_on_screen_lock: document.getElementById('autolock-screen').checked,
+  };
+  withReauth(function () {
+    return invoke('set_autolock_settings', { settings: settings });
+  }).catch(function (e) {
+    loadAutoLockSettings();
+    if (normalizeError(e) !== 'Cancelled') showSettingsStatus(normalizeError(e), 'error');
+  });
+}
+
+['autolock-timeout', 'autolock-hide', 'autolock-suspend', 'autolock-screen'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveAutoLockSettings);
+});
+
+// --- Network health ---
+
+var networkAlerts = {};
+
+function renderNetworkAlerts() {
+  var box = document.getElementById('network-alerts');
+  var kinds = Object.keys(networkAlerts);
+  box.innerHTML = kinds.map(function (k) {
+    return '<span>' + escapeHtml(networkAlerts[k].message) + '</span>';
+  }).join('');
+  box.style.display = kinds.length ? '' : 'none';
+}
+
+function onNetworkAlert(alert) {
+  if (alert.active) {
+    networkAlerts[alert.kind] = alert;
+    if (alert.notify && 'Notification' in window && Notification.permission === 'granted') {
+      new Notification('blocknet', { body: alert.message });
+    }
+  } else {
+    delete networkAlerts[alert.kind];
+  }
+  renderNetworkAlerts();
+}
+
+async function refreshNetworkAlerts() {
+  try {
+    var report = await invoke('get_network_health');
+    networkAlerts = {};
+    report.alerts.forEach(function (a) { networkAlerts[a.kind] = a; });
+    renderNetworkAlerts();
+  } catch (e) {
+    console.error('Network health error:', e);
+  }
+}
+
+var healthSettings = null;
+
+async function loadHealthSettings() {
+  var s = await invoke('get_health_settings');
+  document.getElementById('health-enabled').checked = s.enabled;
+  ensureOption(document.getElementById('health-stall'), s.stall_intervals, 'Stalled after ' + s.stall_intervals + " blocks' time");
+  document.getElementById('health-notify').checked = s.notify;
+  document.getElementById('health-stall').disabled = !s.enabled;
+  document.getElementById('health-notify').disabled = !s.enabled;
+  healthSettings = s;
+}
+
+async function saveHealthSettings() {
+  if (!healthSettings) return;
+  var enabled = document.getElementById('health-enabled').checked;
+  var notify = document.getElementById('health-notify').checked;
+  if (notify && 'Notification' in window && Notification.permission === 'default') {
+    notify = (await Notification.requestPermission()) === 'granted';
+    document.getElementById('health-notify').checked = notify;
+  }
+  document.getElementById('health-stall').disabled = !enabled;
+  document.getElementById('health-notify').disabled = !enabled;
+  healthSettings = Object.assign({}, healthSettings, {
+    enabled: enabled,
+    stall_intervals: parseInt(document.getElementById('health-stall').value, 10) || 0,
+    notify: notify,
+  });
+  try {
+    await invoke('set_health_settings', { settings: healthSettings });
+    if (!enabled) {
+      networkAlerts = {};
+      renderNetworkAlerts();
+    }
+  } catch (e) {
+    showSettingsStatus(normalizeError(e), 'error');
+  }
+}
+
+['health-enabled', 'health-stall', 'health-notify'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveHealthSettings);
+});
+
+// --- Proxy ---
+
+var PROXY_DESC = 'Send network traffic through Tor or another SOCKS5 proxy.';
+
+function setProxyStatus(text) {
+  document.getElementById('proxy-status').textContent = text;
+}
+
+async function loadProxySettings() {
+  var s = await invoke('get_proxy_settings');
+  document.getElementById('proxy-enabled').checked = s.enabled;
+  document.getElementById('proxy-address').value = s.address;
+  document.getElementById('proxy-no-clearnet').checked = s.no_clearnet;
+  document.getElementById('proxy-explorer').value = s.explorer_url;
+  document.getElementById('proxy-explorer').disabled = !s.no_clearnet;
+  setProxyStatus(PROXY_DESC);
+}
+
+async function saveProxySettings() {
+  var noClearnet = document.getElementById('proxy-no-clearnet').checked;
+  document.getElementById('proxy-explorer').disabled = !noClearnet;
+  try {
+    await invoke('set_proxy_settings', {
+      settings: {
+        enabled: document.getElementById('proxy-enabled').checked,
+        address: document.getElementById('proxy-address').value.trim() || '127.0.0.1:9050',
+        no_clearnet: noClearnet,
+        explorer_url: document.getElementById('proxy-explorer').value.trim(),
+      },
+    });
+    setProxyStatus('Saved ; takes effect when the node next starts');
+  } catch (e) {
+    showSettingsStatus(normalizeError(e), 'error');
+  }
+}
+
+['proxy-enabled', 'proxy-address', 'proxy-no-clearnet', 'proxy-explorer'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveProxySettings);
+});
+
+// --- Payment links ---
+
+async function loadUriHandler() {
+  var registered = await invoke('get_uri_handler');
+  var btn = document.getElementById('uri-handler-btn');
+  var status = document.getElementById('uri-handler-status');
+  btn.style.display = registered === false ? '' : 'none';
+  status.textContent = registered === null ? 'Set up by the installer.' :
+    registered ? 'blocknet: links open in this wallet.' :
+    'blocknet: links open in another app, or nowhere.';
+}
+
+document.getElementById('uri-handler-btn').addEventListener('click', async function () {
+  try {
+    await invoke('set_uri_handler');
+    await loadUriHandler();
+  } catch (e) {
+    showSettingsStatus(normalizeError(e), 'error');
+  }
+});
+
+// --- Confirmations ---
+
+async function loadConfirmationSettings() {
+  var s = await invoke('get_confirmation_settings');
+  document.getElementById('confirm-final').value = s.final_confirmations;
+  document.getElementById('confirm-coinbase').value = s.coinbase_final_confirmations;
+}
+
+async function saveConfirmationSettings() {
+  try {
+    await invoke('set_confirmation_settings', {
+      settings: {
+        final_confirmations: parseInt(document.getElementById('confirm-final').value, 10) || 0,
+        coinbase_final_confirmations: parseInt(document.getElementById('confirm-coinbase').value, 10) || 0,
+      },
+    });
+  } catch (e) {
+    showSettingsStatus(normalizeError(e), 'error');
+    await loadConfirmationSettings();
+  }
+}
+
+['confirm-final', 'confirm-coinbase'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveConfirmationSettings);
+});
+
+document.getElementById('proxy-test-btn').addEventListener('click', async function () {
+  var btn = this;
+  btn.disabled = true;
+  setProxyStatus('Testing proxy...');
+  try {
+    var check = await invoke('test_proxy', { address: document.getElementById('proxy-address').value.trim() || '127.0.0.1:9050' });
+    if (!check.reachable) {
+      setProxyStatus('Proxy unreachable ; ' + check.error);
+    } else if (check.is_tor === true) {
+      setProxyStatus('Proxy works ; traffic exits through Tor');
+    } else if (check.is_tor === false) {
+      setProxyStatus('Proxy works ; traffic does not go through Tor');
+    } else {
+      setProxyStatus('SOCKS5 proxy answered ; could not reach the internet through it');
+    }
+  } catch (e) {
+    setProxyStatus(normalizeError(e));
+  } finally {
+    btn.disabled = false;
+  }
+});
+
+// --- Mining schedule ---
+
+var miningSchedule = null;
+var SCHEDULE_DESC = 'Mine between set hours, on AC power, with fewer threads while you work.';
+var SCHEDULE_REASONS = {
+  window: 'paused outside mining hours',
+  battery: 'paused on battery',
+  active: 'paused while the computer is in use',
+  idle: 'mining while idle',
+  scheduled: 'mining with reduced threads while in use',
+};
+
+function hourLabel(h) {
+  return (h < 10 ? '0' : '') + h + ':00';
+}
+
+function ensureOption(select, value, label) {
+  if (!Array.prototype.some.call(select.options, function (o) { return o.value === String(value); })) {
+    var opt = document.createElement('option');
+    opt.value = String(value);
+    opt.textContent = label;
+    select.appendChild(opt);
+  }
+  select.value = String(value);
+}
+
+async function loadMiningSchedule() {
+  var s = await invoke('get_mining_schedule');
+  miningSchedule = s;
+  ['schedule-start', 'schedule-end'].forEach(function (id, i) {
+    var select = document.getElementById(id);
+    if (!select.options.length) {
+      for (var h = 0; h < 24; h++) {
+        var opt = document.createElement('option');
+        opt.value = String(h);
+        opt.textContent = (i === 0 ? 'From ' : 'Until ') + hourLabel(h);
+        select.appendChild(opt);
+      }
+    }
+  });
+  document.getElementById('schedule-enabled').checked = s.enabled;
+  document.getElementById('schedule-start').value = String(s.start_hour);
+  document.getElementById('schedule-end').value = String(s.end_hour);
+  var active = s.idle_only ? 0 : s.active_threads;
+  ensureOption(document.getElementById('schedule-active-threads'), active, active + ' threads while active');
+  ensureOption(document.getElementById('schedule-threads'), s.threads, s.threads + ' threads when idle');
+  document.getElementById('schedule-ac').checked = s.ac_only;
+  // Without it the computer always counts as in use
+  var idleKnown = await invoke('get_idle_detection').catch(function () { return true; });
+  document.getElementById('schedule-desc').textContent = idleKnown
+    ? SCHEDULE_DESC
+    : SCHEDULE_DESC + ' ; idle detection unavailable, so idle threads never start';
+}
+
+function saveMiningSchedule() {
+  if (!miningSchedule) return;
+  var active = parseInt(document.getElementById('schedule-active-threads').value, 10) || 0;
+  miningSchedule = Object.assign({}, miningSchedule, {
+    enabled: document.getElementById('schedule-enabled').checked,
+    start_hour: parseInt(document.getElementById('schedule-start').value, 10) || 0,
+    end_hour: parseInt(document.getElementById('schedule-end').value, 10) || 0,
+    idle_only: active === 0,
+    active_threads: active || miningSchedule.active_threads,
+    threads: parseInt(document.getElementById('schedule-threads').value, 10) || 1,
+    ac_only: document.getElementById('schedule-ac').checked,
+  });
+  invoke('set_mining_schedule', { schedule: miningSchedule })
+    .then(refreshScheduleNote)
+    .catch(function (e) { showSettingsStatus(normalizeError(e), 'error'); });
+}
+
+['schedule-enabled', 'schedule-start', 'schedule-end', 'schedule-active-threads', 'schedule-threads', 'schedule-ac'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveMiningSchedule);
+});
+
+function showScheduleNote(decision) {
+  var note = document.getElementById('mining-schedule-note');
+  if (!decision) {
+    note.style.display = 'none';
+    return;
+  }
+  note.textContent = 'Schedule: ' + (SCHEDULE_REASONS[decision.reason] || decision.reason);
+  note.style.display = '';
+}
+
+async function refreshScheduleNote() {
+  try {
+    showScheduleNote(await invoke('get_mining_schedule_status'));
+  } catch (e) {
+    showScheduleNote(null);
+  }
+}
+
+// --- Mining heat limit ---
+
+async function loadThrottleSettings() {
+  var s = await invoke('get_throttle_settings');
+  document.getElementById('throttle-enabled').checked = s.enabled;
+  ensureOption(document.getElementById('throttle-temp'), s.max_temp_c, 'Below ' + s.max_temp_c + '\u00b0C');
+  ensureOption(document.getElementById('throttle-load'), s.max_load_pct, 'CPU load below ' + s.max_load_pct + '%');
+  var reading = await invoke('get_cpu_reading');
+  if (reading && (reading.temp_c != null || reading.load_pct != null)) {
+    var parts = [];
+    if (reading.temp_c != null) parts.push(reading.temp_c.toFixed(0) + '\u00b0C');
+    if (reading.load_pct != null) parts.push(reading.load_pct.toFixed(0) + '% load');
+    document.getElementById('throttle-reading').textContent = 'CPU now: ' + parts.join(' ; ');
+  }
+}
+
+function saveThrottleSettings() {
+  invoke('set_throttle_settings', {
+    settings: {
+      enabled: document.getElementById('throttle-enabled').checked,
+      max_temp_c: parseFloat(document.getElementById('throttle-temp').value) || 80,
+      max_load_pct: parseFloat(document.getElementById('throttle-load').value) || 0,
+    },
+  }).catch(function (e) { showSettingsStatus(normalizeError(e), 'error'); });
+}
+
+['throttle-enabled', 'throttle-temp', 'throttle-load'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveThrottleSettings);
+});
+
+// --- Password confirmation ---
+
+// Must match reauth::REAUTH_REQUIRED in the backend
+var REAUTH_REQUIRED = 'Password confirmation required';
+
+function promptPassword(error) {
+  return new Promise(function (resolve) {
+    var overlay = document.createElement('div');
+    overlay.className = 'security-blocked-overlay';
+    overlay.innerHTML =
+      '<div class="security-blocked-modal reauth-modal">' +
+        '<h2>Confirm Password</h2>' +
+        '<p>Enter your wallet password to continue.</p>' +
+        '<input type="password" autocomplete="current-password" placeholder="Password">' +
+        '<div class="reauth-error">' + escapeHtml(error || '') + '</div>' +
+        '<button class="btn-primary">Confirm</button>' +
+        '<button class="btn-secondary">Cancel</button>' +
+      '</div>';
+    document.body.appendChild(overlay);
+    var input = overlay.querySelector('input');
+    function finish(value) {
+      overlay.remove();
+      resolve(value);
+    }
+    overlay.querySelector('.btn-primary').addEventListener('click', function () { finish(input.value); });
+    overlay.querySelector('.btn-secondary').addEventListener('click', function () { finish(null); });
+    input.addEventListener('keydown', function (ev) {
+      if (ev.key === 'Enter') finish(input.value);
+      if (ev.key === 'Escape') finish(null);
+    });
+    input.focus();
+  });
+}
+
+// Runs fn, and if the backend wants a fresh password, asks for it once and retries
+async function withReauth(fn) {
+  try {
+    return await fn();
+  } catch (e) {
+    if (normalizeError(e) !== REAUTH_REQUIRED) throw e;
+  }
+  var error = '';
+  for (;;) {
+    var password = await promptPassword(error);
+    if (password === null) throw new Error('Cancelled');
+    try {
+      await invoke('confirm_password', { password: password });
+      break;
+    } catch (e) {
+      error = normalizeError(e);
+    }
+  }
+  return await fn();
+}
+
+async function loadReauthSettings() {
+  var s = await invoke('get_reauth_settings');
+  document.getElementById('reauth-strictness').value = s.strictness;
+  var win = document.getElementById('reauth-window');
+  if (!Array.prototype.some.call(win.options, function (o) { return o.value === String(s.window_secs); })) {
+    var opt = document.createElement('option');
+    opt.value = String(s.window_secs);
+    opt.textContent = 'For ' + s.window_secs + ' seconds';
+    win.appendChild(opt);
+  }
+  win.value = String(s.window_secs);
+  win.disabled = s.strictness === 'always';
+}
+
+function saveReauthSettings() {
+  var strictness = document.getElementById('reauth-strictness').value;
+  document.getElementById('reauth-window').disabled = strictness === 'always';
+  var settings = {
+    strictness: strictness,
+    window_secs: parseInt(document.getElementById('reauth-window').value, 10) || 120,
+  };
+  withReauth(function () {
+    return invoke('set_reauth_settings', { settings: settings });
+  }).catch(function (e) {
+    loadReauthSettings();
+    if (normalizeError(e) !== 'Cancelled') showSettingsStatus(normalizeError(e), 'error');
+  });
+}
+
+['reauth-strictness', 'reauth-window'].forEach(function (id) {
+  document.getElementById(id).addEventListener('change', saveReauthSettings);
+});
+
+// --- Seed backup verification ---
+
+function setBackupNag(unverified) {
+  document.getElementById('backup-nag').style.display = unverified ? '' : 'none';
+}
+
+function showBackupQuiz(positions) {
+  return new Promise(function (resolve) {
+    var overlay = document.createElement('div');
+    overlay.className = 'security-blocked-overlay';
+    overlay.innerHTML =
+      '<div class="security-blocked-modal reauth-modal">' +
+        '<h2>Verify Recovery Seed</h2>' +
+        '<p>Enter these words from the recovery seed you wrote down.</p>' +
+        positions.map(function (p) {
+          return '<label class="backup-quiz-label">Word #' + p + '</label>' +
+            '<input type="text" autocomplete="off" spellcheck="false">';
+        }).join('') +
+        '<div class="reauth-error"></div>' +
+        '<button class="btn-primary">Verify</button>' +
+        '<button class="btn-secondary">Cancel</button>' +
+      '</div>';
+    document.body.appendChild(overlay);
+    var inputs = Array.prototype.slice.call(overlay.querySelectorAll('input'));
+    var errorEl = overlay.querySelector('.reauth-error');
+    var submitBtn = overlay.querySelector('.btn-primary');
+
+    async function submit() {
+      submitBtn.disabled = true;
+      try {
+        var result = await invoke('submit_backup_quiz', {
+          answers: inputs.map(function (el) { return el.value; }),
+        });
+        if (result.verified) {
+          overlay.remove();
+          resolve(true);
+          return;
+        }
+        if (!result.can_retry) {
+          errorEl.textContent = 'Too many wrong answers. Check your written seed and start again.';
+          submitBtn.style.display = 'none';
+          return;
+        }
+        errorEl.textContent = 'The words do not match. Check your written seed and try again.';
+      } catch (e) {
+        errorEl.textContent = normalizeError(e);
+      } finally {
+        submitBtn.disabled = false;
+      }
+    }
+
+    submitBtn.addEventListener('click', submit);
+    overlay.querySelector('.btn-secondary').addEventListener('click', function () {
+      overlay.remove();
+      resolve(false);
+    });
+    inputs.forEach(function (el) {
+      el.addEventListener('keydown', function (ev) {
+        if (ev.key === 'Enter') submit();
+      });
+    });
+    inputs[0].focus();
+  });
+}
+
+async function handleVerifyBackup() {
+  var password = sessionPassword || await promptPassword('');
+  if (!password) return;
+  try {
+    var positions = await withReauth(function () {
+      return invoke('start_backup_quiz', { password: password });
+    });
+    if (await showBackupQuiz(positions)) playTada();
+  } catch (e) {
+    if (normalizeError(e) === 'Cancelled') return;
+    console.error('Seed verification error:', e);
+    document.querySelector('#backup-nag span').textContent = 'Could not start verification: ' + normalizeError(e);
+  }
+}
+
+document.getElementById('backup-verify-btn').addEventListener('click', handleVerifyBackup);
+
+// --- Sync progress ---
+
+function formatEta(secs) {
+  if (secs < 60) return secs + 's';
+  if (secs < 3600) return Math.round(secs / 60) + 'm';
+  var h = Math.floor(secs / 3600);
+  return h + 'h ' + Math.round((secs % 3600) / 60) + 'm';
+}
+
+function renderSyncProgress(p) {
+  var box = document.getElementById('sync-progress');
+  if (!p || !p.syncing) {
+    box.style.display = 'none';
+    return;
+  }
+  box.style.display = '';
+  var pct = Math.min(100, Math.max(0, p.progress * 100));
+  document.getElementById('sync-progress-fill').style.width = pct.toFixed(1) + '%';
+  var label = 'Syncing ' + pct.toFixed(1) + '% ; ' + p.height.toLocaleString() + ' / ~' + p.target_height.toLocaleString() + ' blocks';
+  if (p.blocks_per_sec > 0) label += ' ; ' + p.blocks_per_sec.toFixed(1) + ' blocks/s';
+  if (p.eta_secs) label += ' ; about ' + formatEta(p.eta_secs) + ' left';
+  document.getElementById('sync-progress-label').textContent = label;
+}
+
+if (tauri && tauri.event) {
+  listen('backup-status', function (event) {
+    setBackupNag(event.payload === false);
+  });
+  listen('sync-progress', function (event) {
+    renderSyncProgress(event.payload);
+  });
+  listen('network-alert', function (event) {
+    onNetworkAlert(event.payload);
+  });
+  listen('mining-restore-failed', function (event) {
+    var p = event.payload;
+    var what = p.running ? 'resume mining' : 'restore the thread count';
+    var note = document.getElementById('mining-restore-note');
+    note.textContent = 'Could not ' + what + ' after restart: ' + normalizeError(p.error);
+    note.style.display = '';
+  });
+  listen('mining-throttle', function () {
+    if (currentView === 'mining') loadMining().catch(function () {});
+  });
+  listen('mining-schedule', function (event) {
+    showScheduleNote(event.payload);
+    if (currentView === 'mining') loadMining().catch(function () {});
+  });
+  listen('daemon-stage', function (event) {
+    var stage = event.payload;
+    if (stage.status !== 'waiting' || !daemonStartPromise) return;
+    var el = document.getElementById('password-status');
+    if (el && el.style.display !== 'none' && el.classList.contains('info')) {
+      showStatus(DAEMON_STAGE_LABELS[stage.stage] || 'Preparing daemon...', 'info');
+    }
+  });
+  listen('wallet-locked', function (event) {
+    var app = document.getElementById('app');
+    if (!app || app.style.display === 'none') return;
+    playLock();
+    sessionPassword = '';
+    showUnlockScreen();
+    var reason = event.payload;
+    var msg = reason === 'idle' ? 'Wallet locked after inactivity.' :
+      reason === 'suspend' ? 'Wallet locked after sleep.' :
+      reason === 'screen-lock' ? 'Wallet locked with the screen.' :
+      'Wallet locked.';
+    showStatus(msg + ' Enter password to unlock.', 'info');
+  });
+  // The cached snapshot came from blocks that were reorged out
+  listen('preferred-peers-rejected', function () {
+    showPeerStatus('This node version does not accept preferred peers ; it started without them.', 'error');
+  });
+  listen('wallet-cache-invalidated', function () {
+    dashLastHeight = -1;
+    if (currentView === 'dashboard' || currentView === 'history') loadView(currentView);
+  });
+  // A block holding some of our outputs was replaced; history flags them
+  listen('reorg-detected', function (event) {
+    var r = event.payload;
+    var n = r.outputs.length;
+    if ('Notification' in window && Notification.permission === 'granted') {
+      new Notification('blocknet', { body: 'Chain reorganization at block ' + r.height + ' ; ' + n + ' output' + (n === 1 ? '' : 's') + ' awaiting confirmation again' });
+    }
+    dashLastHeight = -1;
+    if (currentView === 'dashboard' || currentView === 'history') loadView(currentView);
+  });
+}
+
+// --- Launch arguments ---
+// The backend holds launch args and blocknet: links until frontend_ready, then
+// emits them: payment links as parsed payment-request events, wallet files
+// (copied into the app dir) as wallet-file-opened, anything else as
+// instance-args.
+
+function handleLaunchArgs(args) {
+  (args || []).forEach(function (arg) {
+    console.info('Ignoring launch argument:', arg);
+  });
+}
+
+function applyPaymentRequest(req) {
+  var app = document.getElementById('app');
+  if (!app || app.style.display === 'none') {
+    // Wallet is locked; keep the request until it is unlocked
+    pendingPaymentRequest = req;
+    return;
+  }
+  pendingPaymentRequest = null;
+  navigate('send');
+  document.getElementById('send-address').value = req.address;
+  document.getElementById('send-amount').value = req.amount ? formatBNT(req.amount) : '';
+  var note = req.label ? 'Payment request from ' + req.label : 'Payment request loaded';
+  if (req.message) note += ': ' + req.message;
+  showSendStatus(note + '. Review and press Send.', 'info');
+}
+
+if (tauri && tauri.event) {
+  listen('instance-args', function (event) {
+    handleLaunchArgs(event.payload);
+  });
+  listen('payment-request', function (event) {
+    applyPaymentRequest(event.payload);
+  });
+  listen('wallet-file-opened', function (event) {
+    var name = event.payload.replace(/\.dat$/, '');
+    var app = document.getElementById('app');
+    if (app && app.style.display !== 'none') {
+      navigate('settings');
+      showSettingsStatus('Loaded ' + name + ' ; switch to it from the wallet list', 'success');
+    } else {
+      showStatus('Loaded ' + name + '. Unlock, then switch to it in Settings.', 'info');
+    }
+  });
+  listen('wallet-file-error', function (event) {
+    var app = document.getElementById('app');
+    if (app && app.style.display !== 'none') {
+      navigate('settings');
+      showSettingsStatus(normalizeError(event.payload), 'error');
+    } else {
+      showStatus(normalizeError(event.payload), 'error');
+    }
+  });
+  listen('payment-request-error', function (event) {
+    console.error('Rejected payment link:', event.payload);
+    var app = document.getElementById('app');
+    if (app && app.style.display !== 'none') {
+      navigate('send');
+      showSendStatus('Invalid payment link: ' + event.payload, 'error');
+    }
+  });
+}
+
 // --- Keyboard Shortcuts ---
 
 var navKeys = ['dashboard', 'send', 'receive', 'history', 'mining', 'network', 'settings'];
@@ -0,0 +1,133 @@
+// Blocknet stealth addresses are base58 (Bitcoin alphabet) encodings of
+// spend_pub || view_pub, 64 raw bytes. Anything the daemon checks beyond
+// that is its own business; here we only catch typos and truncation that
+// change the decoded length.
+
+const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const ADDRESS_BYTES: usize = 64;
+
+pub const ATOMIC_PER_BNT: u64 = 100_000_000;
+const DECIMALS: usize = 8;
+
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len());
+    for c in s.bytes() {
+        let mut carry = ALPHABET.iter().position(|&a| a == c)? as u32;
+        for b in bytes.iter_mut().rev() {
+            carry += (*b as u32) * 58;
+            *b = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let leading_zeros = s.bytes().take_while(|&c| c == b'1').count();
+    let mut out = vec![0u8; leading_zeros];
+    out.extend(bytes);
+    Some(out)
+}
+
+pub fn validate(address: &str) -> Result<(), String> {
+    if address.is_empty() {
+        return Err("Address is empty".to_string());
+    }
+    match base58_decode(address) {
+        None => Err("Address contains characters outside base58".to_string()),
+        Some(raw) if raw.len() != ADDRESS_BYTES => {
+            Err(format!("Address decodes to {} bytes, expected {}", raw.len(), ADDRESS_BYTES))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+// "1.5" -> 150000000. Rejects more than 8 decimals rather than rounding.
+pub fn parse_bnt(amount: &str) -> Result<u64, String> {
+    let amount = amount.trim();
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && frac.is_empty()) || !digits(whole) || !digits(frac) {
+        return Err(format!("Invalid amount: {}", amount));
+    }
+    if frac.len() > DECIMALS {
+        return Err(format!("Amount has more than {} decimal places", DECIMALS));
+    }
+    let whole: u64 = if whole.is_empty() { 0 } else {
+        whole.parse().map_err(|_| format!("Amount too large: {}", amount))?
+    };
+    let frac: u64 = format!("{:0<width$}", frac, width = DECIMALS).parse().unwrap_or(0);
+    whole
+        .checked_mul(ATOMIC_PER_BNT)
+        .and_then(|w| w.checked_add(frac))
+        .ok_or(format!("Amount too large: {}", amount))
+}
+
+// 150000000 -> "1.5"; the shortest form that round-trips through parse_bnt
+pub fn format_bnt(atomic: u64) -> String {
+    let whole = atomic / ATOMIC_PER_BNT;
+    let frac = atomic % ATOMIC_PER_BNT;
+    if frac == 0 {
+        return whole.to_string();
+    }
+    let frac = format!("{:0width$}", frac, width = DECIMALS);
+    format!("{}.{}", whole, frac.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // base58 of the bytes 1..=64, and of 1..=63 for a truncated one
+    const ADDRESS: &str = "2Ana1pUpv2ZbMVkwF5FXapYeBEjdxDatLn7nvJkhgTSXbs59SyZSx866bXirPgj8QQVB57uxHJBG1YFvkRbFj4T";
+    const SHORT: &str = "GMkH3brNXiNNs1tiFZHu4yZSRrzJwxi5wB9bHFtMinfCXNnR1adh8Vo8NTheK4evneedH4qmvjeqcBBNAefgS";
+
+    #[test]
+    fn validates_length_and_alphabet() {
+        assert!(validate(ADDRESS).is_ok());
+        assert!(validate("").is_err());
+        assert!(validate(SHORT).unwrap_err().contains("63 bytes"));
+        // 0, O, I and l are not in the alphabet
+        assert!(validate(&ADDRESS.replacen('2', "0", 1)).unwrap_err().contains("base58"));
+    }
+
+    #[test]
+    fn parses_bnt_amounts() {
+        let cases: &[(&str, Option<u64>)] = &[
+            ("1", Some(100_000_000)),
+            ("1.5", Some(150_000_000)),
+            (".5", Some(50_000_000)),
+            ("2.", Some(200_000_000)),
+            (" 0.00000001 ", Some(1)),
+            ("12.34567890", Some(1_234_567_890)),
+            ("0.000000001", None),
+            ("1.123456789", None),
+            ("", None),
+            (".", None),
+            ("-1", None),
+            ("1e8", None),
+            ("1,5", None),
+            ("184467440738", None),
+        ];
+        for (input, want) in cases {
+            assert_eq!(parse_bnt(input).ok(), *want, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn nine_decimals_are_refused_not_rounded() {
+        let err = parse_bnt("0.123456789").unwrap_err();
+        assert!(err.contains("more than 8 decimal places"), "{}", err);
+    }
+
+    #[test]
+    fn formats_shortest_round_trip() {
+        for atomic in [0, 1, 10, 50_000_000, 100_000_000, 123_456_789, u64::MAX] {
+            let text = format_bnt(atomic);
+            assert_eq!(parse_bnt(&text), Ok(atomic), "{}", text);
+        }
+        assert_eq!(format_bnt(150_000_000), "1.5");
+        assert_eq!(format_bnt(1), "0.00000001");
+        assert_eq!(format_bnt(200_000_000), "2");
+    }
+}
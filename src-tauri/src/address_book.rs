@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{address, csv, store};
+
+// Saved recipients for the active wallet, in wallet-data/<wallet>/contacts.json
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Contact {
+    pub id: String,
+    pub label: String,
+    pub address: String,
+    #[serde(default)]
+    pub notes: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_used: Option<u64>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct AddressBook {
+    contacts: Vec<Contact>,
+}
+
+#[derive(Serialize)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub skipped: usize,
+    pub invalid: Vec<String>,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn book_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_wallet_data_dir(app)?.join("contacts.json"))
+}
+
+fn load(app: &AppHandle) -> Result<AddressBook, String> {
+    store::load_json(&book_path(app)?)
+}
+
+fn update<R, F: FnOnce(&mut AddressBook) -> Result<R, String>>(app: &AppHandle, f: F) -> Result<R, String> {
+    store::update_json(&book_path(app)?, f)
+}
+
+fn new_id(book: &AddressBook) -> String {
+    let mut n = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    while book.contacts.iter().any(|c| c.id == format!("{:x}", n)) {
+        n += 1;
+    }
+    format!("{:x}", n)
+}
+
+fn clean_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Contact name is required".to_string());
+    }
+    Ok(label.to_string())
+}
+
+// Inserts or, for an address already in the book, reports it as a duplicate
+fn insert(book: &mut AddressBook, label: &str, addr: &str, notes: &str) -> Result<Contact, String> {
+    let label = clean_label(label)?;
+    let addr = addr.trim();
+    address::validate(addr)?;
+    if let Some(existing) = book.contacts.iter().find(|c| c.address == addr) {
+        return Err(format!("Address already saved as {}", existing.label));
+    }
+    let contact = Contact {
+        id: new_id(book),
+        label,
+        address: addr.to_string(),
+        notes: notes.trim().to_string(),
+        created_at: now_secs(),
+        last_used: None,
+    };
+    book.contacts.push(contact.clone());
+    Ok(contact)
+}
+
+fn matches(contact: &Contact, query: &str) -> bool {
+    let q = query.to_lowercase();
+    contact.label.to_lowercase().contains(&q)
+        || contact.address.to_lowercase().contains(&q)
+        || contact.notes.to_lowercase().contains(&q)
+}
+
+#[tauri::command]
+pub async fn list_contacts(app: AppHandle, query: Option<String>) -> Result<Vec<Contact>, String> {
+    let book = load(&app)?;
+    let query = query.unwrap_or_default();
+    let query = query.trim();
+    let mut contacts: Vec<Contact> = book.contacts
+        .into_iter()
+        .filter(|c| query.is_empty() || matches(c, query))
+        .collect();
+    // Recently used first, then alphabetical
+    contacts.sort_by(|a, b| {
+        b.last_used.cmp(&a.last_used)
+            .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+    });
+    Ok(contacts)
+}
+
+#[tauri::command]
+pub async fn add_contact(app: AppHandle, label: String, address: String, notes: Option<String>) -> Result<Contact, String> {
+    update(&app, |book| insert(book, &label, &address, notes.as_deref().unwrap_or("")))
+}
+
+#[tauri::command]
+pub async fn update_contact(
+    app: AppHandle,
+    id: String,
+    label: String,
+    address: String,
+    notes: Option<String>,
+) -> Result<Contact, String> {
+    let label = clean_label(&label)?;
+    let addr = address.trim().to_string();
+    address::validate(&addr)?;
+    update(&app, |book| {
+        if book.contacts.iter().any(|c| c.id != id && c.address == addr) {
+            return Err("Another contact already uses this address".to_string());
+        }
+        let contact = book.contacts
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or("Contact not found".to_string())?;
+        contact.label = label;
+        contact.address = addr;
+        if let Some(notes) = notes {
+            contact.notes = notes.trim().to_string();
+        }
+        Ok(contact.clone())
+    })
+}
+
+#[tauri::command]
+pub async fn delete_contact(app: AppHandle, id: String) -> Result<(), String> {
+    update(&app, |book| {
+        let before = book.contacts.len();
+        book.contacts.retain(|c| c.id != id);
+        if book.contacts.len() == before {
+            return Err("Contact not found".to_string());
+        }
+        Ok(())
+    })
+}
+
+// Stamps last_used on the contact for an address, if there is one
+pub fn mark_used(app: &AppHandle, addr: &str) -> Result<(), String> {
+    update(app, |book| {
+        if let Some(contact) = book.contacts.iter_mut().find(|c| c.address == addr) {
+            contact.last_used = Some(now_secs());
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn mark_contact_used(app: AppHandle, address: String) -> Result<(), String> {
+    mark_used(&app, address.trim())
+}
+
+#[tauri::command]
+pub async fn export_contacts(app: AppHandle, format: String) -> Result<String, String> {
+    let book = load(&app)?;
+    let (ext, contents) = match format.as_str() {
+        "json" => (
+            "json",
+            serde_json::to_string_pretty(&book.contacts)
+                .map_err(|e| format!("Failed to serialize contacts: {}", e))?,
+        ),
+        "csv" => {
+            let mut lines = vec![csv::row(&["label", "address", "notes", "last_used"])];
+            for c in &book.contacts {
+                let last_used = c.last_used.map(|t| t.to_string()).unwrap_or_default();
+                lines.push(csv::row(&[c.label.as_str(), &c.address, &c.notes, &last_used]));
+            }
+            ("csv", lines.join("\n"))
+        }
+        _ => return Err(format!("Unsupported export format: {}", format)),
+    };
+    let wallet = crate::get_active_wallet_name(&app)?;
+    let filename = format!("blocknet-contacts-{}.{}", wallet.trim_end_matches(".dat"), ext);
+    let path = crate::write_download(&app, &filename, contents.as_bytes())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// Rows from a CSV with a label/address header, or a JSON array of objects
+// with label (or name, as the old frontend book stored it) and address.
+fn parse_import(text: &str, is_json: bool) -> Result<Vec<(String, String, String)>, String> {
+    if is_json {
+        let items: Vec<serde_json::Value> = serde_json::from_str(text)
+            .map_err(|e| format!("Invalid contacts JSON: {}", e))?;
+        let field = |v: &serde_json::Value, k: &str| v.get(k).and_then(|x| x.as_str()).unwrap_or("").to_string();
+        return Ok(items
+            .iter()
+            .map(|v| {
+                let label = if v.get("label").is_some() { field(v, "label") } else { field(v, "name") };
+                (label, field(v, "address"), field(v, "notes"))
+            })
+            .collect());
+    }
+    let mut rows = csv::parse(text).into_iter();
+    let header: Vec<String> = rows.next()
+        .ok_or("Contacts file is empty".to_string())?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let col = |name: &str| header.iter().position(|h| h == name);
+    let label_col = col("label").or_else(|| col("name")).ok_or("CSV is missing a label column".to_string())?;
+    let address_col = col("address").ok_or("CSV is missing an address column".to_string())?;
+    let notes_col = col("notes");
+    let get = |r: &Vec<String>, i: Option<usize>| i.and_then(|i| r.get(i)).map(|f| csv::plain(f).to_string()).unwrap_or_default();
+    Ok(rows
+        .map(|r| (get(&r, Some(label_col)), get(&r, Some(address_col)), get(&r, notes_col)))
+        .collect())
+}
+
+pub fn import_text(app: &AppHandle, text: &str, is_json: bool) -> Result<ImportSummary, String> {
+    let entries = parse_import(text, is_json)?;
+    update(app, |book| {
+        let mut summary = ImportSummary { added: 0, skipped: 0, invalid: Vec::new() };
+        for (label, addr, notes) in entries {
+            let addr = addr.trim();
+            if book.contacts.iter().any(|c| c.address == addr) {
+                summary.skipped += 1;
+                continue;
+            }
+            match insert(book, &label, addr, &notes) {
+                Ok(_) => summary.added += 1,
+                Err(e) => {
+                    let name = if label.trim().is_empty() { addr } else { label.trim() };
+                    summary.invalid.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+        Ok(summary)
+    })
+}
+
+#[tauri::command]
+pub async fn import_contacts(app: AppHandle) -> Result<ImportSummary, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    app.dialog()
+        .file()
+        .add_filter("Contacts", &["csv", "json"])
+        .pick_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+
+    let file = rx.await
+        .map_err(|_| "Dialog cancelled".to_string())?
+        .ok_or("No file selected".to_string())?;
+    let path = file.as_path()
+        .ok_or("Invalid file path".to_string())?;
+
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read contacts file: {}", e))?;
+    let is_json = path.extension().map(|e| e.eq_ignore_ascii_case("json")).unwrap_or(false);
+    import_text(&app, &text, is_json)
+}
+
+// One-time hand-over of the book the frontend used to keep in localStorage
+#[tauri::command]
+pub async fn migrate_contacts(app: AppHandle, json: String) -> Result<ImportSummary, String> {
+    import_text(&app, &json, true)
+}
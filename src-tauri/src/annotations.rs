@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::models::{DaemonStats, HistoryResponse, OutputEntry, SendResponse};
+use crate::reorg::OutputConfirmations;
+use crate::{address, address_book, reauth, reorg, store, wallet_cache};
+
+// Local meaning attached to on-chain data, per wallet, in
+// wallet-data/<wallet>/annotations.json. The daemon only knows outputs; what
+// a send was for and who it went to exists nowhere else.
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SendRecord {
+    pub txid: String,
+    pub recipient: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub change: u64,
+    #[serde(default)]
+    pub memo: String,
+    pub sent_at: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct OutputLabel {
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Annotations {
+    #[serde(default)]
+    pub sends: Vec<SendRecord>,
+    // Keyed by "txid:output_index"
+    #[serde(default)]
+    pub outputs: BTreeMap<String, OutputLabel>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct AnnotatedOutput {
+    #[serde(flatten)]
+    pub output: OutputEntry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    // Set on change outputs of a send made through this app
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send: Option<SendRecord>,
+    // Left out when the chain tip is not known
+    #[serde(flatten)]
+    pub confirmation: Option<OutputConfirmations>,
+}
+
+#[derive(Serialize)]
+pub struct AnnotatedHistory {
+    pub count: usize,
+    pub outputs: Vec<AnnotatedOutput>,
+    pub sends: Vec<SendRecord>,
+    // Served from the wallet cache while the daemon syncs
+    pub cached: bool,
+}
+
+pub fn output_key(txid: &str, output_index: u32) -> String {
+    format!("{}:{}", txid, output_index)
+}
+
+fn annotations_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_wallet_data_dir(app)?.join("annotations.json"))
+}
+
+pub fn load(app: &AppHandle) -> Result<Annotations, String> {
+    store::load_json(&annotations_path(app)?)
+}
+
+fn update<R, F: FnOnce(&mut Annotations) -> Result<R, String>>(app: &AppHandle, f: F) -> Result<R, String> {
+    store::update_json(&annotations_path(app)?, f)
+}
+
+pub fn annotate(outputs: Vec<OutputEntry>, notes: &Annotations) -> Vec<AnnotatedOutput> {
+    outputs
+        .into_iter()
+        .map(|output| {
+            let tag = notes.outputs.get(&output_key(&output.txid, output.output_index));
+            let send = notes.sends.iter().find(|s| s.txid == output.txid).cloned();
+            AnnotatedOutput {
+                label: tag.map(|t| t.label.clone()).filter(|s| !s.is_empty()),
+                note: tag.map(|t| t.note.clone()).filter(|s| !s.is_empty()),
+                send,
+                confirmation: None,
+                output,
+            }
+        })
+        .collect()
+}
+
+fn with_notes(app: &AppHandle, outputs: Vec<OutputEntry>, cached: bool) -> Result<AnnotatedHistory, String> {
+    let notes = load(app)?;
+    let outputs = annotate(outputs, &notes);
+    Ok(AnnotatedHistory { count: outputs.len(), outputs, sends: notes.sends, cached })
+}
+
+pub async fn fetch_annotated(app: &AppHandle) -> Result<AnnotatedHistory, String> {
+    let history: HistoryResponse = crate::daemon_json(app, "GET", "/api/wallet/history", None).await?;
+    with_notes(app, history.outputs, false)
+}
+
+#[tauri::command]
+pub async fn get_history(app: AppHandle) -> Result<AnnotatedHistory, String> {
+    let fetch = async {
+        let history: HistoryResponse = crate::daemon_json(&app, "GET", "/api/wallet/history", None).await?;
+        Ok(history.outputs)
+    };
+    let (outputs, cached) =
+        wallet_cache::fresh_or_cached(&app, fetch, |s| s.outputs.clone(), |s, o| s.outputs = Some(o)).await?;
+    let mut history = with_notes(&app, outputs, cached)?;
+    // Confirmations need the daemon's tip, which a cached history was
+    // served for want of
+    let stats = if cached { None } else { crate::daemon_json::<DaemonStats>(&app, "GET", "/api/status", None).await.ok() };
+    if let Some(stats) = stats {
+        let plain: Vec<OutputEntry> = history.outputs.iter().map(|o| o.output.clone()).collect();
+        let mut counts = reorg::confirmations_for(&app, &plain, stats.chain_height);
+        for o in &mut history.outputs {
+            o.confirmation = counts.remove(&output_key(&o.output.txid, o.output.output_index));
+        }
+    }
+    Ok(history)
+}
+
+// Sends through the daemon and keeps the parts of the result the history
+// endpoint will never return: recipient, amount sent and the memo.
+#[tauri::command]
+pub async fn send_funds(
+    app: AppHandle,
+    address: String,
+    amount: u64,
+    memo: Option<String>,
+) -> Result<SendRecord, String> {
+    let recipient = address.trim().to_string();
+    address::validate(&recipient)?;
+    if amount == 0 {
+        return Err("Amount must be greater than zero".to_string());
+    }
+    reauth::require(&app, reauth::Operation::Send)?;
+    let body = serde_json::json!({ "address": recipient, "amount": amount });
+    let res: SendResponse = crate::daemon_json(&app, "POST", "/api/wallet/send", Some(body)).await?;
+
+    let record = SendRecord {
+        txid: res.txid,
+        recipient,
+        amount,
+        fee: res.fee,
+        change: res.change,
+        memo: memo.unwrap_or_default().trim().to_string(),
+        sent_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    // The funds are gone either way; a failed write must not look like a failed send
+    if let Err(e) = update(&app, |a| {
+        a.sends.push(record.clone());
+        Ok(())
+    }) {
+        eprintln!("Failed to record send {}: {}", record.txid, e);
+    }
+    if let Err(e) = address_book::mark_used(&app, &record.recipient) {
+        eprintln!("Failed to update contact: {}", e);
+    }
+    Ok(record)
+}
+
+#[tauri::command]
+pub async fn set_output_label(
+    app: AppHandle,
+    txid: String,
+    output_index: u32,
+    label: String,
+    note: Option<String>,
+) -> Result<(), String> {
+    let key = output_key(&txid, output_index);
+    update(&app, |a| {
+        let label = label.trim().to_string();
+        let note = note.map(|n| n.trim().to_string())
+            .unwrap_or_else(|| a.outputs.get(&key).map(|t| t.note.clone()).unwrap_or_default());
+        if label.is_empty() && note.is_empty() {
+            a.outputs.remove(&key);
+        } else {
+            a.outputs.insert(key, OutputLabel { label, note });
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn set_send_memo(app: AppHandle, txid: String, memo: String) -> Result<(), String> {
+    update(&app, |a| {
+        let send = a.sends
+            .iter_mut()
+            .find(|s| s.txid == txid)
+            .ok_or("No send recorded for this transaction".to_string())?;
+        send.memo = memo.trim().to_string();
+        Ok(())
+    })
+}
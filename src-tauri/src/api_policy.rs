@@ -0,0 +1,50 @@
+// Daemon endpoints the webview may reach through api_call. Anything that
+// moves funds, handles seeds or destroys data has a dedicated command
+// instead (send_funds, import_seed, reveal_seed, purge_blockchain_data) and
+// is refused here. "*" matches one path segment of letters and digits.
+
+const ALLOWED: &[(&str, &str)] = &[
+    ("GET", "/api/status"),
+    ("GET", "/api/block/*"),
+    ("GET", "/api/tx/*"),
+    ("GET", "/api/mempool"),
+    ("GET", "/api/peers"),
+    ("GET", "/api/peers/banned"),
+    ("GET", "/api/wallet/balance"),
+    ("GET", "/api/wallet/address"),
+    ("GET", "/api/wallet/history"),
+    ("GET", "/api/mining"),
+    ("POST", "/api/wallet/load"),
+    ("POST", "/api/wallet/unlock"),
+    ("POST", "/api/wallet/lock"),
+    ("POST", "/api/wallet/sync"),
+    ("POST", "/api/mining/start"),
+    ("POST", "/api/mining/stop"),
+    ("POST", "/api/mining/threads"),
+];
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.split('/');
+    let mut path = path.split('/');
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some("*"), Some(seg)) => {
+                if seg.is_empty() || !seg.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                    return false;
+                }
+            }
+            (Some(p), Some(seg)) if p == seg => {}
+            _ => return false,
+        }
+    }
+}
+
+pub fn check(method: &str, path: &str) -> Result<(), String> {
+    let method = method.to_ascii_uppercase();
+    if ALLOWED.iter().any(|(m, p)| *m == method && matches(p, path)) {
+        Ok(())
+    } else {
+        Err(format!("{} {} is not available to the interface", method, path))
+    }
+}
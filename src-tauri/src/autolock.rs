@@ -0,0 +1,240 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::reauth::Operation;
+
+// The idle clock lives here rather than in the webview: a window hidden in
+// the tray can have its JS timers throttled or frozen entirely.
+const TICK: Duration = Duration::from_secs(5);
+
+// A tick that took far longer than TICK means the machine slept through
+// it. The wall clock shows the gap everywhere; Instant shows it on Windows
+// but stops during sleep on macOS/Linux, so the larger of the two is used.
+const SUSPEND_SLACK: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoLockSettings {
+    // 0 disables the idle timeout
+    pub idle_timeout_secs: u64,
+    pub lock_on_hide: bool,
+    pub lock_on_suspend: bool,
+    pub lock_on_screen_lock: bool,
+}
+
+impl AutoLockSettings {
+    fn locks_less_than(&self, current: &AutoLockSettings) -> bool {
+        // 0 is "never", the longest timeout of all
+        let timeout = |s: &AutoLockSettings| if s.idle_timeout_secs == 0 { u64::MAX } else { s.idle_timeout_secs };
+        timeout(self) > timeout(current)
+            || (current.lock_on_hide && !self.lock_on_hide)
+            || (current.lock_on_suspend && !self.lock_on_suspend)
+            || (current.lock_on_screen_lock && !self.lock_on_screen_lock)
+    }
+}
+
+impl Default for AutoLockSettings {
+    fn default() -> Self {
+        Self {
+            idle_timeout_secs: 15 * 60,
+            lock_on_hide: false,
+            lock_on_suspend: true,
+            lock_on_screen_lock: true,
+        }
+    }
+}
+
+struct Activity {
+    unlocked: bool,
+    last_activity: Instant,
+}
+
+pub struct AutoLockState {
+    activity: Mutex<Activity>,
+    settings: Mutex<AutoLockSettings>,
+}
+
+impl AutoLockState {
+    pub fn new(settings: AutoLockSettings) -> Self {
+        Self {
+            activity: Mutex::new(Activity { unlocked: false, last_activity: Instant::now() }),
+            settings: Mutex::new(settings),
+        }
+    }
+
+    fn settings(&self) -> AutoLockSettings {
+        self.settings.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    fn is_unlocked(&self) -> bool {
+        self.activity.lock().map(|a| a.unlocked).unwrap_or(false)
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.activity.lock().map(|a| a.last_activity.elapsed()).unwrap_or_default()
+    }
+
+    fn set_unlocked(&self, unlocked: bool) {
+        if let Ok(mut a) = self.activity.lock() {
+            a.unlocked = unlocked;
+            a.last_activity = Instant::now();
+        }
+    }
+
+    fn touch(&self) {
+        if let Ok(mut a) = self.activity.lock() {
+            a.last_activity = Instant::now();
+        }
+    }
+}
+
+// Called from set_tray_unlocked so the tray icon and the lock timer never
+// disagree about whether the wallet is open. Every lock, ours or the
+// user's, also drops the confirmation window and the cache key.
+pub fn set_unlocked(app: &AppHandle, unlocked: bool) {
+    app.state::<AutoLockState>().set_unlocked(unlocked);
+    if !unlocked {
+        crate::reauth::clear(app);
+        crate::wallet_cache::forget(app);
+    }
+}
+
+pub fn is_unlocked(app: &AppHandle) -> bool {
+    app.state::<AutoLockState>().is_unlocked()
+}
+
+pub fn touch(app: &AppHandle) {
+    app.state::<AutoLockState>().touch();
+}
+
+pub async fn lock_wallet(app: &AppHandle, reason: &str) -> Result<(), String> {
+    crate::daemon_request(app, "POST", "/api/wallet/lock", None).await?;
+    set_unlocked(app, false);
+    crate::update_tray_icon(app, false)?;
+    let _ = app.emit("wallet-locked", reason);
+    Ok(())
+}
+
+async fn auto_lock(app: &AppHandle, reason: &str) {
+    if let Err(e) = lock_wallet(app, reason).await {
+        eprintln!("Auto-lock ({}) failed: {}", reason, e);
+        // Back off for a full idle period instead of retrying every tick
+        touch(app);
+    }
+}
+
+// Best-effort probe of the OS session lock; false when it cannot be determined
+fn screen_locked() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        crate::background_command("ioreg")
+            .args(["-n", "Root", "-d1"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains("\"CGSSessionScreenIsLocked\"=Yes"))
+            .unwrap_or(false)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let session = std::env::var("XDG_SESSION_ID").unwrap_or_default();
+        if session.is_empty() {
+            return false;
+        }
+        crate::background_command("loginctl")
+            .args(["show-session", &session, "-p", "LockedHint", "--value"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "yes")
+            .unwrap_or(false)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        crate::background_command("tasklist")
+            .args(["/FI", "IMAGENAME eq LogonUI.exe", "/NH"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains("LogonUI.exe"))
+            .unwrap_or(false)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        false
+    }
+}
+
+pub fn window_hidden(app: &AppHandle) {
+    let state = app.state::<AutoLockState>();
+    if state.settings().lock_on_hide && state.is_unlocked() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            auto_lock(&app, "hidden").await;
+        });
+    }
+}
+
+// Why the wallet should lock on this tick, if it should
+fn lock_reason(app: &AppHandle, resumed: bool) -> Option<&'static str> {
+    let state = app.state::<AutoLockState>();
+    if !state.is_unlocked() {
+        return None;
+    }
+    let settings = state.settings();
+    if resumed && settings.lock_on_suspend {
+        Some("suspend")
+    } else if settings.lock_on_screen_lock && screen_locked() {
+        Some("screen-lock")
+    } else if settings.idle_timeout_secs > 0
+        && state.idle_for() >= Duration::from_secs(settings.idle_timeout_secs)
+    {
+        Some("idle")
+    } else {
+        None
+    }
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_wall = SystemTime::now();
+        let mut last_mono = Instant::now();
+        loop {
+            tokio::time::sleep(TICK).await;
+
+            let wall_elapsed = SystemTime::now().duration_since(last_wall).unwrap_or_default();
+            let mono_elapsed = last_mono.elapsed();
+            let resumed = wall_elapsed.max(mono_elapsed) > TICK + SUSPEND_SLACK;
+
+            if let Some(reason) = lock_reason(&app, resumed) {
+                auto_lock(&app, reason).await;
+            }
+            // Taken after the lock work, so a slow lock call does not look
+            // like the machine sleeping through the next tick
+            last_wall = SystemTime::now();
+            last_mono = Instant::now();
+        }
+    });
+}
+
+#[tauri::command]
+pub fn report_activity(state: State<'_, AutoLockState>) {
+    state.touch();
+}
+
+#[tauri::command]
+pub fn get_autolock_settings(state: State<'_, AutoLockState>) -> AutoLockSettings {
+    state.settings()
+}
+
+#[tauri::command]
+pub fn set_autolock_settings(
+    app: AppHandle,
+    state: State<'_, AutoLockState>,
+    settings: AutoLockSettings,
+) -> Result<(), String> {
+    if settings.locks_less_than(&state.settings()) {
+        crate::reauth::require(&app, Operation::LowerProtection)?;
+    }
+    crate::settings::update(&app, |s| s.autolock = settings.clone())?;
+    let mut guard = state.settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    *guard = settings;
+    Ok(())
+}
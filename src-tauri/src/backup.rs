@@ -0,0 +1,177 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::confirm::SeedResponse;
+use crate::reauth::Operation;
+use crate::store;
+
+// Proof that the recovery seed was written down: the user types back a few
+// words at random positions. The seed is fetched and checked here; only the
+// positions and a pass/fail ever reach the webview. Starting a quiz reads
+// the seed, so it needs the same password confirmation as showing it, and
+// starts are spaced out so restarting cannot be used to guess words.
+
+const QUIZ_WORDS: usize = 3;
+const MAX_ATTEMPTS: u32 = 3;
+const QUIZ_TTL: Duration = Duration::from_secs(10 * 60);
+// Between two starts, and after a quiz is used up
+const RESTART_DELAY: Duration = Duration::from_secs(30);
+const LOCKOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BackupStatus {
+    pub verified: bool,
+    pub verified_at: Option<u64>,
+}
+
+struct Quiz {
+    wallet: String,
+    // (1-based position, expected word)
+    expected: Vec<(usize, String)>,
+    attempts: u32,
+    started: Instant,
+}
+
+#[derive(Default)]
+pub struct BackupQuizState {
+    quiz: Mutex<Option<Quiz>>,
+    next_start: Mutex<Option<Instant>>,
+}
+
+impl BackupQuizState {
+    fn hold_starts(&self, delay: Duration) {
+        if let Ok(mut next) = self.next_start.lock() {
+            *next = Some(Instant::now() + delay);
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct QuizResult {
+    pub verified: bool,
+    // False when this quiz is used up and a new one must be started
+    pub can_retry: bool,
+}
+
+fn status_path(app: &AppHandle, wallet: &str) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_wallet_data_dir_for(app, wallet)?.join("backup.json"))
+}
+
+pub fn status(app: &AppHandle) -> Result<BackupStatus, String> {
+    let wallet = crate::get_active_wallet_name(app)?;
+    store::load_json(&status_path(app, &wallet)?)
+}
+
+pub fn mark_verified(app: &AppHandle, wallet: &str) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    store::update_json(&status_path(app, wallet)?, |s: &mut BackupStatus| {
+        s.verified = true;
+        s.verified_at = Some(now);
+        Ok(())
+    })
+}
+
+// Reflects the active wallet's backup state in the tray tooltip and tells
+// the dashboard, so an unverified seed is visible even from the tray.
+pub fn refresh_nag(app: &AppHandle) {
+    let verified = status(app).map(|s| s.verified).unwrap_or(true);
+    let tooltip = if verified { "blocknet" } else { "blocknet ; recovery seed not verified" };
+    if let Ok(guard) = app.state::<crate::TrayState>().icon.lock() {
+        if let Some(tray) = guard.as_ref() {
+            let _ = tray.set_tooltip(Some(tooltip));
+        }
+    }
+    let _ = app.emit("backup-status", verified);
+}
+
+#[tauri::command]
+pub async fn get_backup_status(app: AppHandle) -> Result<BackupStatus, String> {
+    status(&app)
+}
+
+// Returns the positions to ask for
+#[tauri::command]
+pub async fn start_backup_quiz(
+    app: AppHandle,
+    state: State<'_, BackupQuizState>,
+    password: String,
+) -> Result<Vec<usize>, String> {
+    {
+        let next = state.next_start.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(wait) = next.and_then(|t| t.checked_duration_since(Instant::now())) {
+            return Err(format!("Too many attempts. Try again in {} seconds.", wait.as_secs().max(1)));
+        }
+    }
+    crate::reauth::require(&app, Operation::RevealSeed)?;
+    let wallet = crate::get_active_wallet_name(&app)?;
+    let body = serde_json::json!({ "password": password });
+    let seed: SeedResponse = crate::daemon_json(&app, "POST", "/api/wallet/seed", Some(body)).await?;
+    let words: Vec<&str> = seed.mnemonic.split_whitespace().collect();
+    if words.len() < QUIZ_WORDS {
+        return Err("Unexpected recovery seed format".to_string());
+    }
+
+    let mut positions = rand::seq::index::sample(&mut rand::thread_rng(), words.len(), QUIZ_WORDS).into_vec();
+    positions.sort();
+    let expected: Vec<(usize, String)> = positions
+        .iter()
+        .map(|&i| (i + 1, words[i].to_lowercase()))
+        .collect();
+    let asked = expected.iter().map(|(p, _)| *p).collect();
+
+    let mut quiz = state.quiz.lock().map_err(|e| format!("Lock error: {}", e))?;
+    *quiz = Some(Quiz { wallet, expected, attempts: 0, started: Instant::now() });
+    state.hold_starts(RESTART_DELAY);
+    Ok(asked)
+}
+
+// Answers in the order the positions were given
+#[tauri::command]
+pub async fn submit_backup_quiz(
+    app: AppHandle,
+    state: State<'_, BackupQuizState>,
+    answers: Vec<String>,
+) -> Result<QuizResult, String> {
+    let mut guard = state.quiz.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let quiz = guard
+        .as_mut()
+        .filter(|q| q.started.elapsed() < QUIZ_TTL)
+        .ok_or("No verification in progress. Start again.".to_string())?;
+    if quiz.wallet != crate::get_active_wallet_name(&app)? {
+        *guard = None;
+        return Err("The active wallet changed. Start again.".to_string());
+    }
+    if answers.len() != quiz.expected.len() {
+        return Err(format!("Expected {} answers", quiz.expected.len()));
+    }
+
+    // All or nothing: saying which words were wrong would let the quiz be
+    // used to confirm guesses one word at a time
+    let correct = quiz.expected
+        .iter()
+        .zip(&answers)
+        .all(|((_, word), answer)| answer.trim().to_lowercase() == *word);
+
+    if correct {
+        let wallet = quiz.wallet.clone();
+        *guard = None;
+        drop(guard);
+        mark_verified(&app, &wallet)?;
+        refresh_nag(&app);
+        return Ok(QuizResult { verified: true, can_retry: false });
+    }
+
+    quiz.attempts += 1;
+    let can_retry = quiz.attempts < MAX_ATTEMPTS;
+    if !can_retry {
+        *guard = None;
+        state.hold_starts(LOCKOUT);
+    }
+    Ok(QuizResult { verified: false, can_retry })
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::reauth::Operation;
+
+// Destructive daemon operations need a single-use token from
+// request_confirmation, which is only issued after the user accepts a native
+// dialog. Script in the webview can invoke commands but cannot click that.
+
+const TOKEN_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    RevealSeed,
+    PurgeChain,
+}
+
+impl Action {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "reveal-seed" => Ok(Action::RevealSeed),
+            "purge-chain" => Ok(Action::PurgeChain),
+            _ => Err(format!("Unknown action: {}", name)),
+        }
+    }
+
+    // (title, message, confirm button)
+    fn prompt(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Action::RevealSeed => (
+                "Show recovery seed",
+                "Anyone who sees your recovery seed can take your funds. Make sure nobody is watching your screen.",
+                "Show Seed",
+            ),
+            Action::PurgeChain => (
+                "Purge blockchain data",
+                "This deletes all blockchain data and stops the daemon. The wallet file is kept, but the chain must resync from height 0.",
+                "Purge",
+            ),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Confirmations {
+    issued: Mutex<HashMap<String, (Action, Instant)>>,
+}
+
+fn new_token() -> String {
+    let bytes: [u8; 16] = rand::thread_rng().gen();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Returns None when the user declines
+#[tauri::command]
+pub async fn request_confirmation(app: AppHandle, action: String) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+    let action = Action::parse(&action)?;
+    let (title, message, ok) = action.prompt();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(ok.to_string(), "Cancel".to_string()))
+        .show(move |accepted| {
+            let _ = tx.send(accepted);
+        });
+    if !rx.await.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let token = new_token();
+    let state = app.state::<Confirmations>();
+    let mut issued = state.issued.lock().map_err(|e| e.to_string())?;
+    issued.retain(|_, (_, at)| at.elapsed() < TOKEN_TTL);
+    issued.insert(token.clone(), (action, Instant::now()));
+    Ok(Some(token))
+}
+
+fn consume(app: &AppHandle, action: Action, token: &str) -> Result<(), String> {
+    let state = app.state::<Confirmations>();
+    let mut issued = state.issued.lock().map_err(|e| e.to_string())?;
+    match issued.remove(token) {
+        Some((a, at)) if a == action && at.elapsed() < TOKEN_TTL => Ok(()),
+        _ => Err("Confirmation expired or invalid. Please confirm again.".to_string()),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SeedResponse {
+    pub mnemonic: String,
+}
+
+#[tauri::command]
+pub async fn reveal_seed(app: AppHandle, password: String, token: String) -> Result<SeedResponse, String> {
+    consume(&app, Action::RevealSeed, &token)?;
+    crate::reauth::require(&app, Operation::RevealSeed)?;
+    let body = serde_json::json!({ "password": password });
+    crate::daemon_json(&app, "POST", "/api/wallet/seed", Some(body)).await
+}
+
+#[tauri::command]
+pub async fn purge_blockchain_data(app: AppHandle, password: String, token: String) -> Result<serde_json::Value, String> {
+    consume(&app, Action::PurgeChain, &token)?;
+    crate::reauth::require(&app, Operation::ResetChain)?;
+    let body = serde_json::json!({ "password": password, "confirm": true });
+    crate::daemon_json(&app, "POST", "/api/purge", Some(body)).await
+}
@@ -0,0 +1,140 @@
+// Minimal RFC 4180 reading and writing; enough for exports we produce and
+// for spreadsheets that round-trip them.
+
+// Spreadsheets run a cell that starts with one of these as a formula
+const FORMULA_START: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+// Text that a spreadsheet would evaluate gets a leading apostrophe, which
+// makes it show as typed. Numbers such as "-1.5" are left alone.
+fn neutralize(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.starts_with(FORMULA_START) && field.parse::<f64>().is_err() {
+        format!("'{}", field).into()
+    } else {
+        field.into()
+    }
+}
+
+// Undoes neutralize for files we read back in
+pub fn plain(field: &str) -> &str {
+    match field.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_START) && rest.parse::<f64>().is_err() => rest,
+        _ => field,
+    }
+}
+
+pub fn escape(field: &str) -> String {
+    let field = neutralize(field);
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into_owned()
+    }
+}
+
+pub fn row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields.iter().map(|f| escape(f.as_ref())).collect::<Vec<_>>().join(",")
+}
+
+pub fn parse(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|r| !(r.len() == 1 && r[0].is_empty()));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_fields() {
+        let cases = [
+            ("plain", "plain"),
+            ("", ""),
+            ("a,b", "\"a,b\""),
+            ("say \"hi\"", "\"say \"\"hi\"\"\""),
+            ("two\nlines", "\"two\nlines\""),
+            ("crlf\r\nend", "\"crlf\r\nend\""),
+            ("=SUM(A1:A9)", "'=SUM(A1:A9)"),
+            ("+1 555", "'+1 555"),
+            ("@cmd", "'@cmd"),
+            ("-1.5", "-1.5"),
+            ("+2", "+2"),
+            ("=HYPERLINK(\"x\",\"y\")", "\"'=HYPERLINK(\"\"x\"\",\"\"y\"\")\""),
+        ];
+        for (field, want) in cases {
+            assert_eq!(escape(field), want, "{:?}", field);
+        }
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let text = "a,\"b,c\",\"say \"\"hi\"\"\"\r\n\"two\nlines\",,x\n\n";
+        assert_eq!(parse(text), vec![
+            vec!["a", "b,c", "say \"hi\""],
+            vec!["two\nlines", "", "x"],
+        ]);
+        // No trailing newline
+        assert_eq!(parse("a,b"), vec![vec!["a", "b"]]);
+    }
+
+    #[test]
+    fn rows_round_trip() {
+        let fields = [
+            "plain",
+            "a,b",
+            "say \"hi\"",
+            "two\nlines",
+            "=1+1",
+            "-cmd",
+            "\tindent",
+            "-1.5",
+            "'quoted already",
+            "",
+        ];
+        let text = format!("{}\n", row(&fields));
+        let parsed = parse(&text);
+        assert_eq!(parsed.len(), 1);
+        let back: Vec<&str> = parsed[0].iter().map(|f| plain(f)).collect();
+        assert_eq!(back, fields);
+    }
+
+    #[test]
+    fn plain_only_strips_our_apostrophe() {
+        assert_eq!(plain("'=1+1"), "=1+1");
+        assert_eq!(plain("'-1.5"), "'-1.5");
+        assert_eq!(plain("'hello"), "'hello");
+        assert_eq!(plain("=1+1"), "=1+1");
+    }
+}
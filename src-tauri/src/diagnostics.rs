@@ -0,0 +1,381 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::write::DeflateEncoder;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+// A zip of what support needs to see when something breaks. Nothing that
+// identifies the wallet goes in: no wallet files, history, labels or
+// contacts, and addresses, hashes, tokens, credentials, local paths and
+// wallet file names are masked in everything that is included.
+
+const LOG_TAIL_BYTES: u64 = 512 * 1024;
+const SENSITIVE_KEYS: &[&str] = &["password", "token", "secret", "auth", "credential", "mnemonic", "seed", "peer_id"];
+
+// Minimal deflate-only zip writer; the bundle is a handful of small files
+struct Zip {
+    buf: Vec<u8>,
+    central: Vec<u8>,
+    entries: u16,
+    dos_time: u16,
+    dos_date: u16,
+}
+
+impl Zip {
+    fn new() -> Self {
+        use chrono::{Datelike, Timelike};
+        let now = chrono::Local::now();
+        Self {
+            buf: Vec::new(),
+            central: Vec::new(),
+            entries: 0,
+            dos_time: ((now.hour() << 11) | (now.minute() << 5) | (now.second() / 2)) as u16,
+            dos_date: (((now.year().max(1980) - 1980) as u32) << 9 | (now.month() << 5) | now.day()) as u16,
+        }
+    }
+
+    fn add(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+        let mut crc = flate2::Crc::new();
+        crc.update(data);
+        let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).map_err(|e| format!("Failed to compress {}: {}", name, e))?;
+        let compressed = encoder.finish().map_err(|e| format!("Failed to compress {}: {}", name, e))?;
+
+        let offset = self.buf.len() as u32;
+        let name_bytes = name.as_bytes();
+        // Shared tail of the local and central headers: version, UTF-8 flag,
+        // deflate, time, date, crc, sizes, name length, no extra field
+        let mut common = Vec::new();
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0x0800u16.to_le_bytes());
+        common.extend_from_slice(&8u16.to_le_bytes());
+        common.extend_from_slice(&self.dos_time.to_le_bytes());
+        common.extend_from_slice(&self.dos_date.to_le_bytes());
+        common.extend_from_slice(&crc.sum().to_le_bytes());
+        common.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        self.buf.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        self.buf.extend_from_slice(&common);
+        self.buf.extend_from_slice(name_bytes);
+        self.buf.extend_from_slice(&compressed);
+
+        self.central.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        self.central.extend_from_slice(&20u16.to_le_bytes());
+        self.central.extend_from_slice(&common);
+        // comment length, disk number, internal and external attributes
+        self.central.extend_from_slice(&[0u8; 10]);
+        self.central.extend_from_slice(&offset.to_le_bytes());
+        self.central.extend_from_slice(name_bytes);
+        self.entries += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let central_offset = self.buf.len() as u32;
+        let central_size = self.central.len() as u32;
+        self.buf.extend_from_slice(&self.central);
+        self.buf.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        self.buf.extend_from_slice(&[0u8; 4]);
+        self.buf.extend_from_slice(&self.entries.to_le_bytes());
+        self.buf.extend_from_slice(&self.entries.to_le_bytes());
+        self.buf.extend_from_slice(&central_size.to_le_bytes());
+        self.buf.extend_from_slice(&central_offset.to_le_bytes());
+        self.buf.extend_from_slice(&0u16.to_le_bytes());
+        self.buf
+    }
+}
+
+const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn is_peer_id(word: &str) -> bool {
+    (word.starts_with("12D3KooW") || word.starts_with("Qm"))
+        && word.len() >= 46
+        && word.chars().all(|c| BASE58.contains(c))
+}
+
+// Masks stealth addresses (long base58 runs), libp2p peer IDs, full 64-char
+// hashes and the API token wherever they appear in free text
+fn redact_text(text: &str, token: &str) -> String {
+    let text = if token.is_empty() { text.to_string() } else { text.replace(token, "[token]") };
+    let mut out = String::with_capacity(text.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if word.len() >= 80 && word.chars().all(|c| BASE58.contains(c)) {
+            out.push_str("[address]");
+        } else if is_peer_id(word) {
+            out.push_str("[peer]");
+        } else if word.len() == 64 && word.chars().all(|c| c.is_ascii_hexdigit()) {
+            out.push_str("[hash]");
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+// Local paths name the user's account, so known directories become [data],
+// [app] and [home]. Innermost first: the data dir sits inside the app dir,
+// which usually sits inside home.
+fn redact_paths(text: &str, dirs: &[(PathBuf, &str)]) -> String {
+    let mut text = text.to_string();
+    for (dir, label) in dirs {
+        let shown = dir.to_string_lossy();
+        if shown.len() < 2 {
+            continue;
+        }
+        text = text.replace(shown.as_ref(), label);
+        // The daemon writes forward slashes on Windows too
+        text = text.replace(&shown.replace('\\', "/"), label);
+    }
+    text
+}
+
+// Wallet files are named by the user: "savings.dat" -> "[wallet].dat"
+fn redact_wallet_files(text: &str) -> String {
+    const BOUNDARY: &str = "/\\\"'=:,()[]<>`";
+    let lower = text.to_ascii_lowercase();
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut search = 0;
+    while let Some(dot) = lower[search..].find(".dat").map(|i| i + search) {
+        search = dot + 4;
+        if text[search..].chars().next().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let start = text[copied..dot]
+            .rfind(|c: char| c.is_whitespace() || BOUNDARY.contains(c))
+            .map_or(copied, |i| copied + i + 1);
+        if start == dot {
+            continue;
+        }
+        out.push_str(&text[copied..start]);
+        out.push_str("[wallet]");
+        copied = dot;
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+// Everything a log line could identify the user by. Wallet names can hold
+// spaces, so the ones on disk are masked whole before the generic pass.
+fn redact_log(app: &AppHandle, text: &str, token: &str) -> String {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = crate::get_data_dir(app) {
+        dirs.push((dir, "[data]"));
+    }
+    if let Ok(dir) = crate::get_app_dir(app) {
+        dirs.push((dir, "[app]"));
+    }
+    if let Some(dir) = dirs_next::home_dir() {
+        dirs.push((dir, "[home]"));
+    }
+    let mut text = redact_paths(text, &dirs);
+
+    let mut wallets: Vec<String> = crate::get_app_dir(app)
+        .ok()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".dat"))
+        .collect();
+    wallets.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    for name in wallets {
+        text = text.replace(&name, "[wallet].dat");
+    }
+    redact_text(&redact_wallet_files(&text), token)
+}
+
+// Also strips user:pass@ from URLs such as proxy settings
+fn redact_json(value: &mut Value, token: &str) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SENSITIVE_KEYS.iter().any(|k| key.contains(k)) && !v.is_null() {
+                    *v = Value::String("[redacted]".to_string());
+                } else {
+                    redact_json(v, token);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| redact_json(v, token)),
+        Value::String(s) => {
+            if let (Some(scheme), Some(at)) = (s.find("://"), s.find('@')) {
+                if at > scheme {
+                    *s = format!("{}[redacted]{}", &s[..scheme + 3], &s[at..]);
+                }
+            }
+            *s = redact_text(s, token);
+        }
+        _ => {}
+    }
+}
+
+fn pretty(value: &Value) -> Vec<u8> {
+    serde_json::to_vec_pretty(value).unwrap_or_default()
+}
+
+fn log_tail(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    if len > LOG_TAIL_BYTES {
+        file.seek(SeekFrom::Start(len - LOG_TAIL_BYTES)).ok()?;
+    }
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn file_sha256(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let out = crate::background_command(program).args(args).output().ok()?;
+    let text = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if text.is_empty() { None } else { Some(text) }
+}
+
+fn os_version() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        command_output("sw_vers", &["-productVersion"])
+    } else if cfg!(target_os = "windows") {
+        command_output("cmd", &["/C", "ver"])
+    } else {
+        command_output("uname", &["-sr"])
+    }
+}
+
+// The API does not report a version yet, so this falls back to the first
+// line of the running daemon's log that mentions one
+fn daemon_version(status: &Value, log: &Path) -> Option<String> {
+    if let Some(v) = status.get("version").and_then(|v| v.as_str()) {
+        return Some(v.to_string());
+    }
+    let mut text = String::new();
+    std::fs::File::open(log).ok()?.take(64 * 1024).read_to_string(&mut text).ok()?;
+    text.lines()
+        .take(200)
+        .find(|l| l.to_lowercase().contains("version"))
+        .map(|l| l.trim().to_string())
+}
+
+async fn snapshot(app: &AppHandle, path: &str, token: &str) -> Value {
+    match crate::daemon_json::<Value>(app, "GET", path, None).await {
+        Ok(mut value) => {
+            redact_json(&mut value, token);
+            value
+        }
+        Err(e) => serde_json::json!({ "error": redact_text(&e, token) }),
+    }
+}
+
+#[tauri::command]
+pub async fn create_diagnostics_bundle(app: AppHandle) -> Result<Option<String>, String> {
+    let token = crate::read_api_token(&app).unwrap_or_default();
+    let binary = crate::get_binary_path(&app)?;
+    let log_dir = crate::get_log_dir(&app)?;
+    let status = snapshot(&app, "/api/status", &token).await;
+    let version = daemon_version(&status, &log_dir.join("daemon.log")).map(|v| redact_log(&app, &v, &token));
+
+    let manifest = serde_json::json!({
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "app_version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "os_version": os_version(),
+        "arch": std::env::consts::ARCH,
+        "daemon": {
+            "binary": binary.file_name().map(|n| n.to_string_lossy().to_string()),
+            "present": binary.exists(),
+            "sha256": file_sha256(&binary),
+            "version": version,
+        },
+    });
+
+    let mut settings = match crate::settings::try_load(&app) {
+        Ok(settings) => serde_json::to_value(settings).unwrap_or(Value::Null),
+        // The error names the file's path, which would identify the user
+        Err(_) => Value::String("settings.json is unreadable".to_string()),
+    };
+    redact_json(&mut settings, &token);
+
+    let mut zip = Zip::new();
+    zip.add("manifest.json", &pretty(&manifest))?;
+    zip.add("settings.json", &pretty(&settings))?;
+    zip.add("api/status.json", &pretty(&status))?;
+    for (name, path) in [
+        ("api/peers.json", "/api/peers"),
+        ("api/peers-banned.json", "/api/peers/banned"),
+        ("api/mining.json", "/api/mining"),
+    ] {
+        zip.add(name, &pretty(&snapshot(&app, path, &token).await))?;
+    }
+    for name in ["daemon.log", "daemon.log.1", "mining-throttle.log"] {
+        if let Some(text) = log_tail(&log_dir.join(name)) {
+            zip.add(&format!("logs/{}", name), redact_log(&app, &text, &token).as_bytes())?;
+        }
+    }
+
+    let filename = format!("blocknet-diagnostics-{}.zip", chrono::Local::now().format("%Y%m%d%H%M%S"));
+    let path = crate::save_bytes(&app, &filename, &zip.finish(), true).await?;
+    Ok(path.map(|p| p.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_known_directories() {
+        let dirs = [
+            (PathBuf::from("/home/alice/.local/share/com.blocknet.wallet/data"), "[data]"),
+            (PathBuf::from("/home/alice/.local/share/com.blocknet.wallet"), "[app]"),
+            (PathBuf::from("/home/alice"), "[home]"),
+        ];
+        let cases = [
+            ("opened /home/alice/.local/share/com.blocknet.wallet/data/chain.db", "opened [data]/chain.db"),
+            ("wallet=/home/alice/.local/share/com.blocknet.wallet/x.dat", "wallet=[app]/x.dat"),
+            ("cwd /home/alice/src", "cwd [home]/src"),
+            ("/opt/blocknet", "/opt/blocknet"),
+        ];
+        for (line, want) in cases {
+            assert_eq!(redact_paths(line, &dirs), want);
+        }
+    }
+
+    #[test]
+    fn masks_wallet_file_names() {
+        let cases = [
+            ("loaded savings.dat", "loaded [wallet].dat"),
+            ("[app]/Alice-Main.DAT opened", "[app]/[wallet].DAT opened"),
+            ("path=\"C:\\Users\\x\\w1.dat\", ok", "path=\"C:\\Users\\x\\[wallet].dat\", ok"),
+            ("a.dat b.dat", "[wallet].dat [wallet].dat"),
+            ("[wallet].dat", "[wallet].dat"),
+            ("read chain.data and .dat", "read chain.data and .dat"),
+            ("no files here", "no files here"),
+        ];
+        for (line, want) in cases {
+            assert_eq!(redact_wallet_files(line), want);
+        }
+    }
+}
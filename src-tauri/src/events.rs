@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+// Follows the daemon's /api/events stream and hands each event to the
+// module that cares about it. The stream drops whenever the daemon
+// restarts, so it is simply reopened after a short pause.
+
+const RETRY: Duration = Duration::from_secs(5);
+
+fn dispatch(app: &AppHandle, event: &str, data: &str) {
+    match event {
+        "mined_block" => crate::mining_stats::on_mined_block(app, data),
+        "new_block" => {
+            crate::health::on_new_block(app);
+            crate::wallet_cache::on_new_block(app);
+            crate::reorg::on_new_block(app);
+        }
+        _ => {}
+    }
+}
+
+async fn follow(app: &AppHandle) -> Result<(), String> {
+    let token = crate::read_api_token(app)?;
+    let mut resp = reqwest::Client::new()
+        .get("http://127.0.0.1:8332/api/events")
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "text/event-stream")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to open event stream: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Event stream refused: {}", resp.status()));
+    }
+
+    // Bytes, not text: a chunk may end in the middle of a UTF-8 sequence
+    let mut buf: Vec<u8> = Vec::new();
+    let mut event = String::new();
+    let mut data = String::new();
+    while let Some(chunk) = resp.chunk().await.map_err(|e| format!("Event stream failed: {}", e))? {
+        buf.extend_from_slice(&chunk);
+        while let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                if !data.is_empty() {
+                    dispatch(app, if event.is_empty() { "message" } else { &event }, &data);
+                }
+                event.clear();
+                data.clear();
+            } else if let Some(name) = line.strip_prefix("event:") {
+                event = name.trim().to_string();
+            } else if let Some(line) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(line.strip_prefix(' ').unwrap_or(line));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let _ = follow(&app).await;
+            tokio::time::sleep(RETRY).await;
+        }
+    });
+}
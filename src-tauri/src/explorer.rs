@@ -0,0 +1,292 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::models::{Block, DaemonStats, MempoolStats, TxLookup};
+
+// Block, transaction and mempool pages served by the local node, so looking
+// something up does not tell a public explorer which transactions we care
+// about. Blocks and confirmed transactions are only cached once buried
+// deep enough that a reorg will not move them, so a cached height or hash
+// always means the same thing. Confirmations are never cached but worked
+// out against the current tip on every request.
+
+const MAX_BLOCKS: usize = 256;
+const MAX_TXS: usize = 512;
+// Blocks this deep are treated as final for caching
+const SETTLED_DEPTH: u64 = 10;
+const MEMPOOL_TTL: Duration = Duration::from_secs(5);
+
+// Insertion-ordered map that forgets its oldest entries past a limit
+struct Bounded<K, V> {
+    map: HashMap<K, V>,
+    order: VecDeque<K>,
+    limit: usize,
+}
+
+impl<K: std::hash::Hash + Eq + Clone, V: Clone> Bounded<K, V> {
+    fn new(limit: usize) -> Self {
+        Self { map: HashMap::new(), order: VecDeque::new(), limit }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.map.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.map.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > self.limit {
+            if let Some(old) = self.order.pop_front() {
+                self.map.remove(&old);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedTx {
+    tx: serde_json::Value,
+    block_height: u64,
+}
+
+struct Cache {
+    blocks: Bounded<String, Block>,
+    heights: Bounded<u64, String>,
+    txs: Bounded<String, CachedTx>,
+    mempool: Option<(Instant, MempoolStats)>,
+}
+
+pub struct ExplorerState {
+    cache: Mutex<Cache>,
+}
+
+impl Default for ExplorerState {
+    fn default() -> Self {
+        Self {
+            cache: Mutex::new(Cache {
+                blocks: Bounded::new(MAX_BLOCKS),
+                heights: Bounded::new(MAX_BLOCKS),
+                txs: Bounded::new(MAX_TXS),
+                mempool: None,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BlockPage {
+    #[serde(flatten)]
+    pub block: Block,
+    pub confirmations: u64,
+    pub tip_height: u64,
+}
+
+#[derive(Serialize)]
+pub struct TxPage {
+    pub hash: String,
+    pub tx: serde_json::Value,
+    pub fee: u64,
+    pub in_mempool: bool,
+    pub block_height: Option<u64>,
+    pub block_hash: Option<String>,
+    pub timestamp: Option<i64>,
+    pub confirmations: u64,
+    pub tip_height: u64,
+}
+
+#[derive(Serialize)]
+pub struct MempoolPage {
+    #[serde(flatten)]
+    pub stats: MempoolStats,
+    pub tip_height: u64,
+}
+
+#[derive(Serialize)]
+pub struct SearchHit {
+    // block | tx
+    pub kind: &'static str,
+    pub id: String,
+}
+
+fn is_hash(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn confirmations(height: u64, tip: u64) -> u64 {
+    if height > tip { 0 } else { tip - height + 1 }
+}
+
+fn lock(state: &ExplorerState) -> Result<std::sync::MutexGuard<'_, Cache>, String> {
+    state.cache.lock().map_err(|e| format!("Lock error: {}", e))
+}
+
+async fn tip(app: &AppHandle) -> Result<u64, String> {
+    let stats: DaemonStats = crate::daemon_json(app, "GET", "/api/status", None).await?;
+    Ok(stats.chain_height)
+}
+
+// None when the daemon has no such block
+async fn fetch_block(app: &AppHandle, id: &str) -> Result<Option<Block>, String> {
+    let (status, text) = crate::daemon_request_status(app, "GET", &format!("/api/block/{}", id), None).await?;
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(text);
+    }
+    serde_json::from_str(&text).map(Some).map_err(|e| format!("Unexpected block from daemon: {}", e))
+}
+
+// `id` is a height or a block hash
+async fn block(app: &AppHandle, state: &ExplorerState, id: &str, tip: u64) -> Result<Option<Block>, String> {
+    let id = id.to_lowercase();
+    let cached = {
+        let cache = lock(state)?;
+        let hash = match id.parse::<u64>() {
+            Ok(height) => cache.heights.get(&height),
+            Err(_) => Some(id.clone()),
+        };
+        hash.and_then(|h| cache.blocks.get(&h))
+    };
+    if let Some(block) = cached {
+        return Ok(Some(block));
+    }
+
+    // Near the tip a block may still be reorged out, so only settled ones
+    // are kept
+    let Some(block) = fetch_block(app, &id).await? else { return Ok(None) };
+    if confirmations(block.height, tip) >= SETTLED_DEPTH {
+        let mut cache = lock(state)?;
+        cache.heights.insert(block.height, block.hash.clone());
+        cache.blocks.insert(block.hash.clone(), block.clone());
+    }
+    Ok(Some(block))
+}
+
+fn check_block_id(id: &str) -> Result<(), String> {
+    if id.parse::<u64>().is_ok() || is_hash(id) {
+        Ok(())
+    } else {
+        Err(format!("Not a block height or hash: {}", id))
+    }
+}
+
+fn check_tx_hash(hash: &str) -> Result<(), String> {
+    if is_hash(hash) { Ok(()) } else { Err(format!("Not a transaction hash: {}", hash)) }
+}
+
+#[tauri::command]
+pub async fn explorer_block(
+    app: AppHandle,
+    state: State<'_, ExplorerState>,
+    id: String,
+) -> Result<BlockPage, String> {
+    let id = id.trim();
+    check_block_id(id)?;
+    let tip = tip(&app).await?;
+    let block = block(&app, &state, id, tip).await?.ok_or_else(|| format!("Block not found: {}", id))?;
+    Ok(BlockPage { confirmations: confirmations(block.height, tip), tip_height: tip, block })
+}
+
+#[tauri::command]
+pub async fn explorer_tx(
+    app: AppHandle,
+    state: State<'_, ExplorerState>,
+    hash: String,
+) -> Result<TxPage, String> {
+    let hash = hash.trim().to_lowercase();
+    check_tx_hash(&hash)?;
+    let tip = tip(&app).await?;
+
+    let cached = lock(&state)?.txs.get(&hash);
+    let (tx, block_height) = match cached {
+        Some(c) => (c.tx, Some(c.block_height)),
+        None => {
+            let path = format!("/api/tx/{}", hash);
+            let (status, text) = crate::daemon_request_status(&app, "GET", &path, None).await?;
+            if status == reqwest::StatusCode::NOT_FOUND {
+                return Err(format!("Transaction not found: {}", hash));
+            }
+            if !status.is_success() {
+                return Err(text);
+            }
+            let found: TxLookup = serde_json::from_str(&text)
+                .map_err(|e| format!("Unexpected transaction from daemon: {}", e))?;
+            let block_height = found.block_height.filter(|_| !found.in_mempool);
+            if let Some(h) = block_height.filter(|h| confirmations(*h, tip) >= SETTLED_DEPTH) {
+                lock(&state)?.txs.insert(hash.clone(), CachedTx { tx: found.tx.clone(), block_height: h });
+            }
+            (found.tx, block_height)
+        }
+    };
+
+    let containing = match block_height {
+        Some(h) => block(&app, &state, &h.to_string(), tip).await?,
+        None => None,
+    };
+    Ok(TxPage {
+        fee: tx.get("fee").and_then(|f| f.as_u64()).unwrap_or(0),
+        in_mempool: block_height.is_none(),
+        confirmations: block_height.map(|h| confirmations(h, tip)).unwrap_or(0),
+        block_hash: containing.as_ref().map(|b| b.hash.clone()),
+        timestamp: containing.as_ref().map(|b| b.timestamp),
+        block_height,
+        tip_height: tip,
+        hash,
+        tx,
+    })
+}
+
+#[tauri::command]
+pub async fn explorer_mempool(app: AppHandle, state: State<'_, ExplorerState>) -> Result<MempoolPage, String> {
+    let tip = tip(&app).await?;
+    let fresh = lock(&state)?
+        .mempool
+        .as_ref()
+        .filter(|(at, _)| at.elapsed() < MEMPOOL_TTL)
+        .map(|(_, stats)| stats.clone());
+    let stats = match fresh {
+        Some(stats) => stats,
+        None => {
+            let stats: MempoolStats = crate::daemon_json(&app, "GET", "/api/mempool", None).await?;
+            lock(&state)?.mempool = Some((Instant::now(), stats.clone()));
+            stats
+        }
+    };
+    Ok(MempoolPage { stats, tip_height: tip })
+}
+
+// Decides what a search box entry refers to. A 64-character hash is tried
+// as a block first, since a transaction lookup scans the chain.
+#[tauri::command]
+pub async fn explorer_search(
+    app: AppHandle,
+    state: State<'_, ExplorerState>,
+    query: String,
+) -> Result<SearchHit, String> {
+    let query = query.trim().to_lowercase();
+    if query.parse::<u64>().is_ok() {
+        return Ok(SearchHit { kind: "block", id: query });
+    }
+    if !is_hash(&query) {
+        return Err("Enter a block height, block hash or transaction hash".to_string());
+    }
+    if lock(&state)?.txs.get(&query).is_some() {
+        return Ok(SearchHit { kind: "tx", id: query });
+    }
+    let tip = tip(&app).await?;
+    if block(&app, &state, &query, tip).await?.is_some() {
+        return Ok(SearchHit { kind: "block", id: query });
+    }
+    let path = format!("/api/tx/{}", query);
+    let (status, _) = crate::daemon_request_status(&app, "GET", &path, None).await?;
+    if status.is_success() {
+        return Ok(SearchHit { kind: "tx", id: query });
+    }
+    Err(format!("Nothing found for {}", query))
+}
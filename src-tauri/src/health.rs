@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::models::{BannedList, DaemonStats};
+
+// Watches whether the node is really part of the network. Three alerts,
+// each raised once and cleared when the condition goes away: no peers, a
+// tip that has stopped moving for several expected block intervals, and a
+// burst of new bans. Alerts go out as network-alert events; the webview
+// turns them into a banner and, if enabled, a system notification.
+
+const TICK: Duration = Duration::from_secs(15);
+// Ticks in a row with zero peers before it counts
+const NO_PEERS_TICKS: u32 = 2;
+const BAN_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthSettings {
+    pub enabled: bool,
+    // Expected block intervals without a new block before the tip is stale
+    pub stall_intervals: u32,
+    // New bans within ten minutes that count as a spike
+    pub ban_spike: u32,
+    pub notify: bool,
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self { enabled: true, stall_intervals: 6, ban_spike: 5, notify: false }
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct Alert {
+    // no-peers | stalled | ban-spike
+    pub kind: &'static str,
+    pub active: bool,
+    pub message: String,
+    pub notify: bool,
+}
+
+#[derive(Clone, Default, Serialize)]
+pub struct HealthReport {
+    pub peers: u32,
+    pub chain_height: u64,
+    pub secs_since_block: Option<u64>,
+    pub banned: u32,
+    pub alerts: Vec<Alert>,
+}
+
+#[derive(Default)]
+struct Monitor {
+    height: u64,
+    last_block: Option<Instant>,
+    zero_peer_ticks: u32,
+    bans: VecDeque<(Instant, u32)>,
+    active: BTreeMap<&'static str, Alert>,
+    report: HealthReport,
+}
+
+pub struct HealthState {
+    settings: Mutex<HealthSettings>,
+    monitor: Mutex<Monitor>,
+}
+
+impl HealthState {
+    pub fn new(settings: HealthSettings) -> Self {
+        Self { settings: Mutex::new(settings), monitor: Mutex::new(Monitor::default()) }
+    }
+
+    fn settings(&self) -> HealthSettings {
+        self.settings.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+// Called from events for every new_block on the daemon's event stream
+pub fn on_new_block(app: &AppHandle) {
+    if let Ok(mut m) = app.state::<HealthState>().monitor.lock() {
+        m.last_block = Some(Instant::now());
+    }
+}
+
+fn format_minutes(secs: u64) -> String {
+    match secs / 60 {
+        0 => format!("{} seconds", secs),
+        1 => "1 minute".to_string(),
+        m => format!("{} minutes", m),
+    }
+}
+
+// Raises or clears one alert, returning it when that is news
+fn set_alert(m: &mut Monitor, kind: &'static str, raised: Option<String>, notify: bool) -> Option<Alert> {
+    match (raised, m.active.contains_key(kind)) {
+        (Some(message), false) => {
+            let alert = Alert { kind, active: true, message, notify };
+            m.active.insert(kind, alert.clone());
+            Some(alert)
+        }
+        (None, true) => {
+            let mut alert = m.active.remove(kind)?;
+            alert.active = false;
+            alert.notify = false;
+            Some(alert)
+        }
+        _ => None,
+    }
+}
+
+fn evaluate(
+    m: &mut Monitor,
+    stats: &DaemonStats,
+    banned: u32,
+    block_secs: f64,
+    settings: &HealthSettings,
+) -> Vec<Alert> {
+    let now = Instant::now();
+    if stats.chain_height != m.height || m.last_block.is_none() {
+        m.height = stats.chain_height;
+        m.last_block = Some(now);
+    }
+    m.zero_peer_ticks = if stats.peers == 0 { m.zero_peer_ticks + 1 } else { 0 };
+    m.bans.push_back((now, banned));
+    while m.bans.front().is_some_and(|(t, _)| now.duration_since(*t) > BAN_WINDOW) {
+        m.bans.pop_front();
+    }
+
+    let since_block = m.last_block.map(|t| now.duration_since(t).as_secs()).unwrap_or(0);
+    let stall_after = (block_secs * settings.stall_intervals as f64) as u64;
+    let oldest_bans = m.bans.front().map(|(_, c)| *c).unwrap_or(banned);
+    let new_bans = banned.saturating_sub(oldest_bans);
+
+    let no_peers = (m.zero_peer_ticks >= NO_PEERS_TICKS)
+        .then(|| "The node has no peers and cannot see the network".to_string());
+    let stalled = (settings.stall_intervals > 0 && stats.peers > 0 && since_block > stall_after)
+        .then(|| format!("No new block for {} ; the chain may be stuck", format_minutes(since_block)));
+    let ban_spike = (settings.ban_spike > 0 && new_bans >= settings.ban_spike)
+        .then(|| format!("{} peers banned in the last 10 minutes", new_bans));
+
+    let mut news = Vec::new();
+    news.extend(set_alert(m, "no-peers", no_peers, settings.notify));
+    news.extend(set_alert(m, "stalled", stalled, settings.notify));
+    news.extend(set_alert(m, "ban-spike", ban_spike, settings.notify));
+
+    m.report = HealthReport {
+        peers: stats.peers,
+        chain_height: stats.chain_height,
+        secs_since_block: Some(since_block),
+        banned,
+        alerts: m.active.values().cloned().collect(),
+    };
+    news
+}
+
+async fn tick(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<HealthState>();
+    let settings = state.settings();
+    let stats: DaemonStats = crate::daemon_json(app, "GET", "/api/status", None).await?;
+    let banned: BannedList = crate::daemon_json(app, "GET", "/api/peers/banned", None).await?;
+    let block_secs = crate::sync::avg_block_interval(app).unwrap_or(crate::sync::FALLBACK_BLOCK_SECS);
+
+    let news = {
+        let mut m = state.monitor.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let news = evaluate(&mut m, &stats, banned.count.max(banned.banned.len() as u32), block_secs, &settings);
+        if !settings.enabled {
+            // Still tracked, so turning alerts back on raises what is current
+            m.active.clear();
+            m.report.alerts.clear();
+            Vec::new()
+        } else {
+            news
+        }
+    };
+    for alert in news {
+        let _ = app.emit("network-alert", alert);
+    }
+    Ok(())
+}
+
+// A stopped daemon is not a network problem; start over when it is back
+fn reset(app: &AppHandle) {
+    let state = app.state::<HealthState>();
+    let cleared: Vec<Alert> = match state.monitor.lock() {
+        Ok(mut m) => {
+            let cleared = std::mem::take(&mut m.active)
+                .into_values()
+                .map(|mut a| {
+                    a.active = false;
+                    a.notify = false;
+                    a
+                })
+                .collect();
+            *m = Monitor::default();
+            cleared
+        }
+        Err(_) => Vec::new(),
+    };
+    for alert in cleared {
+        let _ = app.emit("network-alert", alert);
+    }
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            if tick(&app).await.is_err() {
+                reset(&app);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_network_health(state: State<'_, HealthState>) -> HealthReport {
+    state.monitor.lock().map(|m| m.report.clone()).unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_health_settings(state: State<'_, HealthState>) -> HealthSettings {
+    state.settings()
+}
+
+#[tauri::command]
+pub fn set_health_settings(
+    app: AppHandle,
+    state: State<'_, HealthState>,
+    settings: HealthSettings,
+) -> Result<(), String> {
+    crate::settings::update(&app, |s| s.health = settings.clone())?;
+    *state.settings.lock().map_err(|e| format!("Lock error: {}", e))? = settings;
+    Ok(())
+}
@@ -0,0 +1,322 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::address::format_bnt;
+use crate::annotations::{self, AnnotatedOutput, SendRecord};
+use crate::{csv, opener, store};
+
+// History export written by the backend straight to a user-chosen file.
+// Outputs come from /api/wallet/history merged with local labels; block
+// timestamps are resolved through /api/block/{height}.
+
+const TIMESTAMP_LOOKUPS: usize = 8;
+
+#[derive(Default, Deserialize)]
+pub struct ExportFilter {
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+    // Inclusive, YYYY-MM-DD in UTC
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BlockHeader {
+    timestamp: i64,
+}
+
+#[derive(Serialize)]
+struct JsonOutput<'a> {
+    #[serde(flatten)]
+    output: &'a AnnotatedOutput,
+    timestamp: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct JsonSend<'a> {
+    #[serde(flatten)]
+    send: &'a SendRecord,
+    block_height: Option<u64>,
+}
+
+fn parse_day(s: &str, end_of_day: bool) -> Result<i64, String> {
+    let date = chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date {}, expected YYYY-MM-DD", s))?;
+    let time = if end_of_day {
+        chrono::NaiveTime::from_hms_opt(23, 59, 59)
+    } else {
+        chrono::NaiveTime::from_hms_opt(0, 0, 0)
+    };
+    Ok(date.and_time(time.unwrap_or_default()).and_utc().timestamp())
+}
+
+fn iso_time(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_default()
+}
+
+fn output_type(o: &AnnotatedOutput) -> &'static str {
+    if o.output.is_coinbase {
+        "mining_reward"
+    } else if o.send.is_some() {
+        "change"
+    } else if o.output.spent {
+        "sent"
+    } else {
+        "received"
+    }
+}
+
+struct Filter {
+    from_height: u64,
+    to_height: u64,
+    from_ts: Option<i64>,
+    to_ts: Option<i64>,
+}
+
+impl Filter {
+    fn new(f: ExportFilter) -> Result<Self, String> {
+        Ok(Self {
+            from_height: f.from_height.unwrap_or(0),
+            to_height: f.to_height.unwrap_or(u64::MAX),
+            from_ts: f.from_date.as_deref().filter(|s| !s.is_empty()).map(|s| parse_day(s, false)).transpose()?,
+            to_ts: f.to_date.as_deref().filter(|s| !s.is_empty()).map(|s| parse_day(s, true)).transpose()?,
+        })
+    }
+
+    fn keep(&self, height: Option<u64>, ts: Option<i64>) -> bool {
+        if let Some(h) = height {
+            if h < self.from_height || h > self.to_height {
+                return false;
+            }
+        } else if self.from_height > 0 || self.to_height < u64::MAX {
+            return false;
+        }
+        match ts {
+            Some(ts) => self.from_ts.is_none_or(|f| ts >= f) && self.to_ts.is_none_or(|t| ts <= t),
+            None => self.from_ts.is_none() && self.to_ts.is_none(),
+        }
+    }
+}
+
+// A few block lookups in flight at once; one at a time is slow for long
+// histories, all at once would flood the daemon
+async fn resolve_timestamps(app: &AppHandle, heights: &BTreeSet<u64>) -> HashMap<u64, i64> {
+    let mut out = HashMap::new();
+    let mut heights = heights.iter().copied();
+    let mut pending = tokio::task::JoinSet::new();
+    loop {
+        while pending.len() < TIMESTAMP_LOOKUPS {
+            let Some(h) = heights.next() else { break };
+            let app = app.clone();
+            pending.spawn(async move {
+                let path = format!("/api/block/{}", h);
+                (h, crate::daemon_json::<BlockHeader>(&app, "GET", &path, None).await)
+            });
+        }
+        let Some(done) = pending.join_next().await else { break };
+        match done {
+            Ok((h, Ok(b))) => {
+                out.insert(h, b.timestamp);
+            }
+            Ok((h, Err(e))) => eprintln!("Failed to resolve timestamp for block {}: {}", h, e),
+            Err(e) => eprintln!("Timestamp lookup failed: {}", e),
+        }
+    }
+    out
+}
+
+async fn pick_path(app: &AppHandle, format: &str) -> Result<Option<std::path::PathBuf>, String> {
+    let stamp = chrono::Local::now().format("%Y%m%d%H%M%S");
+    let (name, ext, label) = match format {
+        "csv" => (format!("blocknet-history-{}.csv", stamp), "csv", "CSV"),
+        "json" => (format!("blocknet-history-{}.json", stamp), "json", "JSON"),
+        "accounting" => (format!("blocknet-accounting-{}.csv", stamp), "csv", "CSV"),
+        _ => return Err(format!("Unsupported export format: {}", format)),
+    };
+    crate::pick_save_path(app, &name, label, &[ext]).await
+}
+
+#[tauri::command]
+pub async fn export_history(
+    app: AppHandle,
+    format: String,
+    filter: Option<ExportFilter>,
+) -> Result<Option<String>, String> {
+    let filter = Filter::new(filter.unwrap_or_default())?;
+    let Some(path) = pick_path(&app, &format).await? else {
+        return Ok(None);
+    };
+
+    let history = annotations::fetch_annotated(&app).await?;
+    let send_heights: HashMap<&str, u64> = history.outputs
+        .iter()
+        .filter(|o| o.send.is_some())
+        .map(|o| (o.output.txid.as_str(), o.output.block_height))
+        .collect();
+    let heights: BTreeSet<u64> = history.outputs.iter().map(|o| o.output.block_height).collect();
+    let times = resolve_timestamps(&app, &heights).await;
+
+    let mut outputs: Vec<(&AnnotatedOutput, Option<i64>)> = history.outputs
+        .iter()
+        .map(|o| (o, times.get(&o.output.block_height).copied()))
+        .filter(|(o, ts)| filter.keep(Some(o.output.block_height), *ts))
+        .collect();
+    outputs.sort_by_key(|(o, _)| o.output.block_height);
+
+    // Sends confirm with their change output; until then only sent_at is known
+    let mut sends: Vec<(&SendRecord, Option<u64>, i64)> = history.sends
+        .iter()
+        .map(|s| {
+            let height = send_heights.get(s.txid.as_str()).copied();
+            let ts = height.and_then(|h| times.get(&h).copied()).unwrap_or(s.sent_at as i64);
+            (s, height, ts)
+        })
+        .filter(|(_, h, ts)| filter.keep(*h, Some(*ts)))
+        .collect();
+    sends.sort_by_key(|(_, _, ts)| *ts);
+
+    // Stream into a temp file and rename, so a failed export never leaves a
+    // truncated file where the user expects a complete one
+    let tmp = store::temp_path(&path);
+    let result = write_export(&app, &format, &tmp, &outputs, &sends).await;
+    match result.and_then(|_| std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to save export: {}", e))) {
+        Ok(()) => {
+            opener::remember(&app, &path);
+            Ok(Some(path.to_string_lossy().to_string()))
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            Err(e)
+        }
+    }
+}
+
+async fn write_export(
+    app: &AppHandle,
+    format: &str,
+    path: &std::path::Path,
+    outputs: &[(&AnnotatedOutput, Option<i64>)],
+    sends: &[(&SendRecord, Option<u64>, i64)],
+) -> Result<(), String> {
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut w = std::io::BufWriter::new(file);
+    let io_err = |e: std::io::Error| format!("Failed to write export: {}", e);
+
+    match format {
+        "csv" => {
+            writeln!(w, "{}", csv::row(&[
+                "txid", "output_index", "amount_bnt", "block_height", "timestamp", "type",
+                "spent", "spent_height", "label", "note", "memo",
+            ])).map_err(io_err)?;
+            for (o, ts) in outputs {
+                let memo = o.send.as_ref().map(|s| s.memo.as_str()).unwrap_or("");
+                writeln!(w, "{}", csv::row(&[
+                    o.output.txid.clone(),
+                    o.output.output_index.to_string(),
+                    format_bnt(o.output.amount),
+                    o.output.block_height.to_string(),
+                    ts.map(iso_time).unwrap_or_default(),
+                    output_type(o).to_string(),
+                    o.output.spent.to_string(),
+                    o.output.spent_height.map(|h| h.to_string()).unwrap_or_default(),
+                    o.label.clone().unwrap_or_default(),
+                    o.note.clone().unwrap_or_default(),
+                    memo.to_string(),
+                ])).map_err(io_err)?;
+            }
+        }
+        "json" => {
+            // Written entry by entry rather than as one serialized value
+            let json_err = |e: serde_json::Error| format!("Failed to write export: {}", e);
+            let wallet = serde_json::to_string(&crate::get_active_wallet_name(app)?).map_err(json_err)?;
+            let exported_at = iso_time(chrono::Utc::now().timestamp());
+            write!(w, "{{\n  \"wallet\": {},\n  \"exported_at\": \"{}\",\n  \"outputs\": [", wallet, exported_at)
+                .map_err(io_err)?;
+            for (i, (o, ts)) in outputs.iter().enumerate() {
+                w.write_all(if i == 0 { b"\n    " } else { b",\n    " }).map_err(io_err)?;
+                serde_json::to_writer(&mut w, &JsonOutput { output: o, timestamp: *ts }).map_err(json_err)?;
+            }
+            write!(w, "\n  ],\n  \"sends\": [").map_err(io_err)?;
+            for (i, (s, h, _)) in sends.iter().enumerate() {
+                w.write_all(if i == 0 { b"\n    " } else { b",\n    " }).map_err(io_err)?;
+                serde_json::to_writer(&mut w, &JsonSend { send: s, block_height: *h }).map_err(json_err)?;
+            }
+            writeln!(w, "\n  ]\n}}").map_err(io_err)?;
+        }
+        "accounting" => {
+            // Column layout accepted by the common crypto tax importers.
+            // Change outputs of sends made here are not income and are
+            // skipped; change from sends made elsewhere cannot be told apart.
+            writeln!(w, "{}", csv::row(&[
+                "Date", "Sent Amount", "Sent Currency", "Received Amount", "Received Currency",
+                "Fee Amount", "Fee Currency", "Label", "Description", "TxHash",
+            ])).map_err(io_err)?;
+            // Received outputs and sends each in time order, merged as
+            // they are written
+            let mut received: Vec<&(&AnnotatedOutput, Option<i64>)> =
+                outputs.iter().filter(|(o, _)| o.send.is_none()).collect();
+            received.sort_by_key(|(o, ts)| (ts.unwrap_or(0), o.output.block_height));
+            let mut received = received.into_iter().peekable();
+            let mut sends = sends.iter().peekable();
+            loop {
+                let take_send = match (received.peek(), sends.peek()) {
+                    (None, None) => break,
+                    (Some((_, ts)), Some((_, _, send_ts))) => *send_ts < ts.unwrap_or(0),
+                    (None, Some(_)) => true,
+                    (Some(_), None) => false,
+                };
+                let row = if take_send {
+                    let Some((s, _, ts)) = sends.next() else { break };
+                    accounting_send(s, *ts)
+                } else {
+                    let Some((o, ts)) = received.next() else { break };
+                    accounting_received(o, *ts)
+                };
+                writeln!(w, "{}", csv::row(&row)).map_err(io_err)?;
+            }
+        }
+        _ => return Err(format!("Unsupported export format: {}", format)),
+    }
+    w.flush().map_err(io_err)
+}
+
+fn accounting_received(o: &AnnotatedOutput, ts: Option<i64>) -> Vec<String> {
+    let desc = [o.label.as_deref(), o.note.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ; ");
+    vec![
+        ts.map(iso_time).unwrap_or_default(),
+        String::new(),
+        String::new(),
+        format_bnt(o.output.amount),
+        "BNT".to_string(),
+        String::new(),
+        String::new(),
+        if o.output.is_coinbase { "mining".to_string() } else { String::new() },
+        desc,
+        o.output.txid.clone(),
+    ]
+}
+
+fn accounting_send(s: &SendRecord, ts: i64) -> Vec<String> {
+    vec![
+        iso_time(ts),
+        format_bnt(s.amount),
+        "BNT".to_string(),
+        String::new(),
+        String::new(),
+        format_bnt(s.fee),
+        "BNT".to_string(),
+        String::new(),
+        s.memo.clone(),
+        s.txid.clone(),
+    ]
+}
@@ -1,10 +1,41 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::sync::Mutex;
-use tauri::{AppHandle, Manager, State, WindowEvent};
+use tauri::{AppHandle, Emitter, Manager, State, WindowEvent};
 use tauri::menu::{Menu, MenuItem};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
 
+mod address;
+mod address_book;
+mod annotations;
+mod api_policy;
+mod autolock;
+mod backup;
+mod confirm;
+mod csv;
+mod diagnostics;
+mod events;
+mod explorer;
+mod health;
+mod history_export;
+mod mining_resume;
+mod mining_schedule;
+mod mining_stats;
+mod mnemonic;
+mod models;
+mod opener;
+mod payment_uri;
+mod peers;
+mod proxy;
+mod readiness;
+mod reauth;
+mod reorg;
+mod settings;
+mod single_instance;
+mod store;
+mod sync;
+mod throttle;
+mod wallet_cache;
 
 struct DaemonState {
     child: Mutex<Option<std::process::Child>>,
@@ -121,11 +152,36 @@ fn get_wallet_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     Ok(get_app_dir(app)?.join(&name))
 }
 
+// Per-wallet app state (address book, labels, ...) kept next to, never inside,
+// the wallet file. Follows the wallet through rename and delete.
+fn get_wallet_data_dir_for(app: &AppHandle, name: &str) -> Result<std::path::PathBuf, String> {
+    Ok(get_app_dir(app)?.join("wallet-data").join(name))
+}
+
+fn get_wallet_data_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let name = get_active_wallet_name(app)?;
+    get_wallet_data_dir_for(app, &name)
+}
+
 // Compat shim used by commands that need (data_dir, wallet_path)
 fn get_paths(app: &AppHandle) -> Result<(std::path::PathBuf, std::path::PathBuf), String> {
     Ok((get_data_dir(app)?, get_wallet_path(app)?))
 }
 
+// For the small console tools we shell out to while running; on Windows
+// each one would otherwise flash a terminal window.
+fn background_command(program: &str) -> std::process::Command {
+    #[allow(unused_mut)]
+    let mut cmd = std::process::Command::new(program);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
 fn kill_port_8332() {
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
@@ -192,6 +248,55 @@ async fn create_wallet(app: AppHandle, password: String) -> Result<(), String> {
     Ok(())
 }
 
+fn get_log_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(get_app_dir(app)?.join("logs"))
+}
+
+// Whether the daemon's last log complains about a command-line flag, as
+// Go's flag package does for one it does not know
+fn daemon_rejected_flag(app: &AppHandle, flag: &str) -> bool {
+    let name = flag.trim_start_matches('-');
+    get_log_dir(app)
+        .and_then(|dir| std::fs::read_to_string(dir.join("daemon.log")).map_err(|e| e.to_string()))
+        .map(|log| log.lines().any(|l| l.contains("flag") && l.contains(&format!("-{}", name))))
+        .unwrap_or(false)
+}
+
+fn spawn_daemon(app: &AppHandle, binary_path: &std::path::Path, args: &[String]) -> Result<std::process::Child, String> {
+    let (stdout, stderr) = match open_daemon_log(app) {
+        Ok(log) => match log.try_clone() {
+            Ok(copy) => (std::process::Stdio::from(log), std::process::Stdio::from(copy)),
+            Err(_) => (std::process::Stdio::from(log), std::process::Stdio::null()),
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            (std::process::Stdio::null(), std::process::Stdio::null())
+        }
+    };
+
+    std::process::Command::new(binary_path)
+        .args(args)
+        .stdout(stdout)
+        .stderr(stderr)
+        .spawn()
+        .map_err(|e| {
+            if check_security_blocked(binary_path) {
+                return "SECURITY_BLOCKED".to_string();
+            }
+            format!("Failed to spawn daemon: {}", e)
+        })
+}
+
+// Each start gets a fresh daemon.log; the previous run is kept as
+// daemon.log.1 so a crash can still be looked at after restarting.
+fn open_daemon_log(app: &AppHandle) -> Result<std::fs::File, String> {
+    let dir = get_log_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create log dir: {}", e))?;
+    let log = dir.join("daemon.log");
+    let _ = std::fs::rename(&log, dir.join("daemon.log.1"));
+    std::fs::File::create(&log).map_err(|e| format!("Failed to open daemon log: {}", e))
+}
+
 #[tauri::command]
 async fn start_daemon(app: AppHandle, state: State<'_, DaemonState>) -> Result<(), String> {
     let (data_dir, wallet_path) = get_paths(&app)?;
@@ -199,8 +304,10 @@ async fn start_daemon(app: AppHandle, state: State<'_, DaemonState>) -> Result<(
         .map_err(|e| format!("Failed to create data dir: {}", e))?;
     let binary_path = get_binary_path(&app)?;
 
-    // Clean stale cookie
-    let _ = std::fs::remove_file(data_dir.join("api.cookie"));
+    // Clean stale cookie, but never one a running daemon still uses
+    if readiness::daemon_gone(&app).await {
+        let _ = std::fs::remove_file(data_dir.join("api.cookie"));
+    }
 
     let mut args = vec![
         "--daemon".to_string(),
@@ -212,21 +319,37 @@ async fn start_daemon(app: AppHandle, state: State<'_, DaemonState>) -> Result<(
     // (needed for /api/wallet/import filename resolution)
     args.push("--wallet".to_string());
     args.push(wallet_path.to_str().unwrap().to_string());
+    let proxy_args = proxy::startup_args(&app)?;
+    let proxied = !proxy_args.is_empty();
+    args.extend(proxy_args);
+    let peer_args = peers::startup_args(&app);
 
-    let mut child = std::process::Command::new(&binary_path)
-        .args(&args)
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn()
-        .map_err(|e| {
-            if check_security_blocked(&binary_path) {
-                return "SECURITY_BLOCKED".to_string();
-            }
-            format!("Failed to spawn daemon: {}", e)
-        })?;
-
+    let mut child = spawn_daemon(&app, &binary_path, &[args.clone(), peer_args.clone()].concat())?;
     std::thread::sleep(std::time::Duration::from_secs(2));
 
+    // A daemon that does not know --seed must not keep the node from
+    // starting at all; it runs without the preferred peers instead
+    if !peer_args.is_empty()
+        && matches!(child.try_wait(), Ok(Some(status)) if !status.success())
+        && daemon_rejected_flag(&app, peers::SEED_FLAG)
+    {
+        eprintln!("Daemon does not accept {}, starting without preferred peers", peers::SEED_FLAG);
+        let _ = app.emit("preferred-peers-rejected", ());
+        child = spawn_daemon(&app, &binary_path, &args)?;
+        std::thread::sleep(std::time::Duration::from_secs(2));
+    }
+
+    // Unlike preferred peers, the proxy is never dropped to get the node up
+    if proxied
+        && matches!(child.try_wait(), Ok(Some(status)) if !status.success())
+        && daemon_rejected_flag(&app, proxy::PROXY_FLAG)
+    {
+        return Err(format!(
+            "This node version does not accept {}, so it was not started. Turn the proxy off in Settings to run without it.",
+            proxy::PROXY_FLAG
+        ));
+    }
+
     match child.try_wait() {
         Ok(Some(status)) if !status.success() => {
             #[cfg(unix)]
@@ -241,10 +364,21 @@ async fn start_daemon(app: AppHandle, state: State<'_, DaemonState>) -> Result<(
             }
             Err(format!("Daemon exited with code: {}", status))
         },
-        Ok(Some(_)) => Ok(()),
+        // A clean exit is only fine when another daemon already serves the
+        // API; otherwise nothing is left to become ready
+        Ok(Some(status)) => {
+            if !readiness::listener_up().await {
+                return Err(format!("Daemon exited right after starting with code: {}", status));
+            }
+            mining_schedule::reapply(&app);
+            mining_resume::arm(&app);
+            Ok(())
+        },
         Ok(None) => {
             let mut guard = state.child.lock().map_err(|e| format!("Lock error: {}", e))?;
             *guard = Some(child);
+            mining_schedule::reapply(&app);
+            mining_resume::arm(&app);
             Ok(())
         },
         Err(e) => Err(format!("Failed to check daemon status: {}", e)),
@@ -278,24 +412,50 @@ async fn check_daemon_ready(app: AppHandle) -> Result<bool, String> {
     match res {
         Ok(r) if r.status().is_success() => Ok(true),
         _ => {
-            // Stale cookie from a previous run ; clean it up
-            let _ = std::fs::remove_file(&cookie_path);
+            // A failed request may just be a daemon still starting; the
+            // cookie is only stale once nothing is left that could own it
+            if readiness::daemon_gone(&app).await {
+                let _ = std::fs::remove_file(&cookie_path);
+            }
             Ok(false)
         }
     }
 }
 
-#[tauri::command]
-async fn api_call(app: AppHandle, method: String, path: String, body: Option<String>) -> Result<String, String> {
-    let data_dir = get_data_dir(&app)?;
-    let token = std::fs::read_to_string(data_dir.join("api.cookie"))
+fn read_api_token(app: &AppHandle) -> Result<String, String> {
+    let data_dir = get_data_dir(app)?;
+    std::fs::read_to_string(data_dir.join("api.cookie"))
         .map(|s| s.trim().to_string())
-        .map_err(|e| format!("Failed to read auth cookie: {}", e))?;
+        .map_err(|e| format!("Failed to read auth cookie: {}", e))
+}
+
+async fn daemon_request(app: &AppHandle, method: &str, path: &str, body: Option<String>) -> Result<String, String> {
+    let (status, text) = daemon_request_status(app, method, path, body).await?;
+    if !status.is_success() {
+        return Err(text);
+    }
+    Ok(text)
+}
 
-    let client = reqwest::Client::new();
+// Status and body without treating non-2xx as an error, for callers that
+// handle an older daemon's 404 differently from a real failure
+async fn daemon_request_status(
+    app: &AppHandle,
+    method: &str,
+    path: &str,
+    body: Option<String>,
+) -> Result<(reqwest::StatusCode, String), String> {
+    let token = read_api_token(app)?;
+
+    // Generous enough for sends and wallet loads, but a wedged daemon must
+    // not hold up the background loops that call through here forever
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
     let url = format!("http://127.0.0.1:8332{}", path);
 
-    let mut req = match method.as_str() {
+    let mut req = match method {
         "POST" => client.post(&url),
         "PUT" => client.put(&url),
         "DELETE" => client.delete(&url),
@@ -308,13 +468,43 @@ async fn api_call(app: AppHandle, method: String, path: String, body: Option<Str
     }
 
     let res = req.send().await.map_err(|e| format!("Request failed: {}", e))?;
-
-    if !res.status().is_success() {
-        let text = res.text().await.unwrap_or_default();
-        return Err(text);
+    let status = res.status();
+    if !status.is_success() {
+        return Ok((status, res.text().await.unwrap_or_default()));
     }
+    let text = res.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+    Ok((status, text))
+}
+
+async fn daemon_json<T: serde::de::DeserializeOwned>(
+    app: &AppHandle,
+    method: &str,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> Result<T, String> {
+    let text = daemon_request(app, method, path, body.map(|b| b.to_string())).await?;
+    serde_json::from_str(&text).map_err(|e| format!("Unexpected response from {}: {}", path, e))
+}
 
-    res.text().await.map_err(|e| format!("Failed to read response: {}", e))
+#[tauri::command]
+async fn api_call(app: AppHandle, method: String, path: String, body: Option<String>) -> Result<String, String> {
+    api_policy::check(&method, &path)?;
+    let response = daemon_request(&app, &method, &path, body.clone()).await?;
+    if path.starts_with("/api/mining/") {
+        mining_resume::remember(&app, &path, body.as_deref());
+    }
+    if path == "/api/wallet/load" || path == "/api/wallet/unlock" {
+        let password = body
+            .as_deref()
+            .and_then(|b| serde_json::from_str::<serde_json::Value>(b).ok())
+            .and_then(|v| v.get("password").and_then(|p| p.as_str()).map(str::to_string));
+        if let Some(password) = password {
+            wallet_cache::unlock(&app, password).await;
+        }
+    } else if path == "/api/wallet/lock" {
+        wallet_cache::forget(&app);
+    }
+    Ok(response)
 }
 
 #[tauri::command]
@@ -325,6 +515,7 @@ async fn stop_daemon(state: State<'_, DaemonState>) -> Result<(), String> {
 
 #[tauri::command]
 async fn reset_blockchain_data(app: AppHandle, state: State<'_, DaemonState>) -> Result<(), String> {
+    reauth::require(&app, reauth::Operation::ResetChain)?;
     stop_daemon_inner(&state);
     kill_port_8332();
     std::thread::sleep(std::time::Duration::from_millis(500));
@@ -339,37 +530,152 @@ async fn reset_blockchain_data(app: AppHandle, state: State<'_, DaemonState>) ->
     Ok(())
 }
 
-#[tauri::command]
-async fn save_file(app: AppHandle, filename: String, contents: String) -> Result<String, String> {
+// Reduces an untrusted name to a single plain file name: no directories,
+// no characters Windows rejects, no reserved device names.
+fn sanitize_filename(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or("");
+    let cleaned: String = base
+        .chars()
+        .map(|c| if c.is_control() || "<>:\"|?*".contains(c) { '_' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').trim().to_string();
+    let stem = cleaned.split('.').next().unwrap_or("").to_ascii_uppercase();
+    let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem.as_bytes()[3].is_ascii_digit());
+    if cleaned.is_empty() {
+        "download".to_string()
+    } else if reserved {
+        format!("_{}", cleaned)
+    } else {
+        cleaned
+    }
+}
+
+// Creates "report.csv", or "report (1).csv", "report (2).csv", ... when
+// taken. The create itself is the check, so a file appearing in between
+// is never replaced.
+fn create_unique(dir: &std::path::Path, filename: &str) -> Result<(std::path::PathBuf, std::fs::File), String> {
+    let (stem, ext) = match filename.rfind('.') {
+        Some(i) if i > 0 => (&filename[..i], &filename[i..]),
+        _ => (filename, ""),
+    };
+    for n in 0..10_000 {
+        let path = if n == 0 { dir.join(filename) } else { dir.join(format!("{} ({}){}", stem, n, ext)) };
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+        }
+    }
+    Err(format!("Failed to find a free name for {} in {}", filename, dir.display()))
+}
+
+fn downloads_dir(app: &AppHandle) -> std::path::PathBuf {
     let downloads = if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
         dirs_next::download_dir()
             .or_else(|| dirs_next::home_dir().map(|h| h.join("Downloads")))
     } else {
         dirs_next::download_dir()
     };
-    let dir = downloads.unwrap_or_else(|| {
+    downloads.unwrap_or_else(|| {
         app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
-    });
+    })
+}
+
+// Writes into Downloads without ever replacing an existing file. The empty
+// file create_unique claims holds the name while the contents go to a
+// sibling temp file, which is then renamed over it, so the name never shows
+// a half-written file.
+fn write_download(app: &AppHandle, filename: &str, contents: &[u8]) -> Result<std::path::PathBuf, String> {
+    let dir = downloads_dir(app);
     std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create dir: {}", e))?;
-    let path = dir.join(&filename);
-    std::fs::write(&path, &contents).map_err(|e| format!("Failed to write file: {}", e))?;
-    Ok(path.to_string_lossy().to_string())
+
+    let (path, placeholder) = create_unique(&dir, &sanitize_filename(filename))?;
+    drop(placeholder);
+    if let Err(e) = store::write_atomic(&path, contents) {
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+    opener::remember(app, &path);
+    Ok(path)
 }
 
+// Native save dialog; Ok(None) when the user cancels
+async fn pick_save_path(
+    app: &AppHandle,
+    default_name: &str,
+    filter_name: &str,
+    extensions: &[&str],
+) -> Result<Option<std::path::PathBuf>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let mut dialog = app.dialog()
+        .file()
+        .set_file_name(default_name)
+        .add_filter(filter_name, extensions);
+    if let Some(dir) = dirs_next::download_dir() {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.save_file(move |file_path| {
+        let _ = tx.send(file_path);
+    });
+
+    let file = rx.await.map_err(|_| "Dialog cancelled".to_string())?;
+    match file {
+        Some(f) => Ok(Some(f.into_path().map_err(|e| format!("Invalid file path: {}", e))?)),
+        None => Ok(None),
+    }
+}
+
+// With `dialog` the user picks the destination and the OS dialog handles
+// overwrite prompts; otherwise the file lands in Downloads under a
+// non-colliding name. None when the dialog is cancelled.
+async fn save_bytes(
+    app: &AppHandle,
+    filename: &str,
+    data: &[u8],
+    dialog: bool,
+) -> Result<Option<std::path::PathBuf>, String> {
+    let filename = sanitize_filename(filename);
+    if !dialog {
+        return write_download(app, &filename, data).map(Some);
+    }
+    let ext = std::path::Path::new(&filename)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let filter = if ext.is_empty() { "All Files".to_string() } else { ext.to_uppercase() };
+    let exts: Vec<&str> = if ext.is_empty() { vec!["*"] } else { vec![ext.as_str()] };
+    match pick_save_path(app, &filename, &filter, &exts).await? {
+        Some(path) => {
+            store::write_atomic(&path, data)?;
+            opener::remember(app, &path);
+            Ok(Some(path))
+        }
+        None => Ok(None),
+    }
+}
+
+// `contents` for text, `bytes` for binary (QR PNGs, PDFs)
 #[tauri::command]
-async fn open_file(path: String) -> Result<(), String> {
-    let cmd = if cfg!(target_os = "macos") {
-        "open"
-    } else if cfg!(target_os = "windows") {
-        "explorer"
-    } else {
-        "xdg-open"
+async fn save_file(
+    app: AppHandle,
+    filename: String,
+    contents: Option<String>,
+    bytes: Option<Vec<u8>>,
+    dialog: Option<bool>,
+) -> Result<Option<String>, String> {
+    let data = match (contents, bytes) {
+        (Some(text), None) => text.into_bytes(),
+        (None, Some(bytes)) => bytes,
+        _ => return Err("Provide exactly one of contents or bytes".to_string()),
     };
-    std::process::Command::new(cmd)
-        .arg(&path)
-        .spawn()
-        .map_err(|e| format!("Failed to open file: {}", e))?;
-    Ok(())
+    let path = save_bytes(&app, &filename, &data, dialog.unwrap_or(false)).await?;
+    Ok(path.map(|p| p.to_string_lossy().to_string()))
 }
 
 // --- Wallet management ---
@@ -439,6 +745,11 @@ async fn rename_wallet(app: AppHandle, old_name: String, new_name: String) -> Re
     }
     std::fs::rename(&old_path, &new_path)
         .map_err(|e| format!("Rename failed: {}", e))?;
+    let old_data = get_wallet_data_dir_for(&app, &old_name)?;
+    if old_data.exists() {
+        std::fs::rename(&old_data, get_wallet_data_dir_for(&app, &new_name)?)
+            .map_err(|e| format!("Failed to move wallet data: {}", e))?;
+    }
     // If the renamed wallet was the active one, update the active reference
     let active = get_active_wallet_name(&app)?;
     if active == old_name {
@@ -456,12 +767,18 @@ async fn delete_wallet(app: AppHandle, name: String) -> Result<(), String> {
     if name == active {
         return Err("Cannot delete the active wallet".to_string());
     }
+    reauth::require(&app, reauth::Operation::DeleteWallet)?;
     let path = get_app_dir(&app)?.join(&name);
     if !path.exists() {
         return Err(format!("Wallet file not found: {}", name));
     }
     std::fs::remove_file(&path)
         .map_err(|e| format!("Delete failed: {}", e))?;
+    let data_dir = get_wallet_data_dir_for(&app, &name)?;
+    if data_dir.exists() {
+        std::fs::remove_dir_all(&data_dir)
+            .map_err(|e| format!("Failed to remove wallet data: {}", e))?;
+    }
     Ok(())
 }
 
@@ -485,6 +802,12 @@ async fn import_wallet_file(app: AppHandle) -> Result<String, String> {
     let source = file.as_path()
         .ok_or("Invalid file path".to_string())?;
 
+    copy_wallet_file(&app, source)
+}
+
+// Copies a wallet file into the app dir under its own name; shared with
+// wallet files opened from the OS, which arrive as launch args
+fn copy_wallet_file(app: &AppHandle, source: &std::path::Path) -> Result<String, String> {
     let filename = source.file_name()
         .ok_or("Invalid filename".to_string())?
         .to_string_lossy()
@@ -496,7 +819,7 @@ async fn import_wallet_file(app: AppHandle) -> Result<String, String> {
         format!("{}.dat", filename)
     };
 
-    let app_dir = get_app_dir(&app)?;
+    let app_dir = get_app_dir(app)?;
     std::fs::create_dir_all(&app_dir)
         .map_err(|e| format!("Failed to create app dir: {}", e))?;
     let dest = app_dir.join(&filename);
@@ -511,8 +834,69 @@ async fn import_wallet_file(app: AppHandle) -> Result<String, String> {
     Ok(filename)
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ImportResponse {
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    filename: String,
+}
+
+// Brings the daemon back with no wallet loaded
+async fn restart_daemon(app: &AppHandle, state: State<'_, DaemonState>) -> Result<(), String> {
+    stop_daemon_inner(&state);
+    kill_port_8332();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    start_daemon(app.clone(), state).await?;
+    readiness::wait_until_ready(app).await
+}
+
+// The phrase is checked here before it ever reaches the daemon.
+// /api/wallet/import refuses with 409 while any wallet is loaded, so that
+// case restarts the daemon into a clean state and retries once.
 #[tauri::command]
-fn set_tray_unlocked(unlocked: bool, tray_state: State<'_, TrayState>) -> Result<(), String> {
+async fn import_seed(
+    app: AppHandle,
+    state: State<'_, DaemonState>,
+    mnemonic: String,
+    password: String,
+    filename: Option<String>,
+) -> Result<ImportResponse, String> {
+    let mnemonic = mnemonic::validate(&mnemonic)?;
+    if password.chars().count() < 3 {
+        return Err("Password must be at least 3 characters".to_string());
+    }
+    let filename = filename.as_deref().map(str::trim).filter(|n| !n.is_empty()).map(|name| {
+        let name = sanitize_filename(name);
+        if name.ends_with(".dat") { name } else { format!("{}.dat", name) }
+    });
+    let mut body = serde_json::json!({ "mnemonic": mnemonic, "password": password });
+    if let Some(name) = &filename {
+        body["filename"] = serde_json::Value::String(name.clone());
+    }
+
+    let result: ImportResponse = match daemon_json(&app, "POST", "/api/wallet/import", Some(body.clone())).await {
+        Err(e) if e.contains("wallet already loaded") => {
+            restart_daemon(&app, state).await?;
+            daemon_json(&app, "POST", "/api/wallet/import", Some(body)).await?
+        }
+        result => result?,
+    };
+    // Restoring from the phrase is proof enough that it is written down.
+    // Without a filename the daemon writes to its --wallet path.
+    let written = match (&result.filename, filename) {
+        (name, _) if !name.is_empty() => name.clone(),
+        (_, Some(name)) => name,
+        _ => get_active_wallet_name(&app)?,
+    };
+    if let Err(e) = backup::mark_verified(&app, &written) {
+        eprintln!("Failed to record seed backup: {}", e);
+    }
+    Ok(result)
+}
+
+fn update_tray_icon(app: &AppHandle, unlocked: bool) -> Result<(), String> {
+    let tray_state = app.state::<TrayState>();
     let mut guard = tray_state.icon.lock().map_err(|e| e.to_string())?;
     if let Some(tray) = guard.as_mut() {
         let img = if unlocked {
@@ -526,16 +910,87 @@ fn set_tray_unlocked(unlocked: bool, tray_state: State<'_, TrayState>) -> Result
     Ok(())
 }
 
+#[tauri::command]
+fn set_tray_unlocked(app: AppHandle, unlocked: bool) -> Result<(), String> {
+    autolock::set_unlocked(&app, unlocked);
+    update_tray_icon(&app, unlocked)?;
+    if unlocked {
+        backup::refresh_nag(&app);
+        mining_resume::on_unlocked(&app);
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() {
+    let context = tauri::generate_context!();
+
+    // Finder passes a -psn_ process serial number on older macOS; not ours.
+    // File paths are made absolute, since a forwarding launch may have been
+    // started from another working directory.
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with("-psn_"))
+        .map(|a| match std::fs::canonicalize(&a) {
+            Ok(path) => path.to_string_lossy().to_string(),
+            Err(_) => a,
+        })
+        .collect();
+
+    // Same location Tauri resolves for app_data_dir()
+    let instance = match dirs_next::data_dir().map(|d| d.join(&context.config().identifier)) {
+        Some(app_dir) => match single_instance::acquire(&app_dir) {
+            Ok(Some(guard)) => Some(guard),
+            Ok(None) => {
+                if let Err(e) = single_instance::forward(&app_dir, &args) {
+                    eprintln!("{}", e);
+                }
+                return;
+            }
+            Err(e) => {
+                eprintln!("Single-instance check failed: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
     tauri::Builder::default()
         .manage(DaemonState { child: Mutex::new(None) })
         .manage(TrayState { icon: Mutex::new(None) })
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_fs::init())
+        .manage(single_instance::LaunchArgs::new(args))
+        .manage(opener::OpenablePaths::default())
+        .manage(confirm::Confirmations::default())
+        .manage(backup::BackupQuizState::default())
+        .manage(sync::SyncState::default())
+        .manage(mining_stats::MiningStatsState::default())
+        .manage(mining_resume::MiningResumeState::default())
+        .manage(explorer::ExplorerState::default())
+        .manage(wallet_cache::WalletCacheState::default())
+        .manage(reorg::ReorgState::default())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_window_state::Builder::new().build())
-        .setup(|app| {
+        .setup(move |app| {
+            if let Some(guard) = instance {
+                single_instance::listen(guard, app.handle().clone());
+            }
+
+            let settings = settings::load(app.handle());
+            app.manage(autolock::AutoLockState::new(settings.autolock));
+            app.manage(reauth::ReauthState::new(settings.reauth));
+            app.manage(mining_schedule::MiningScheduleState::new(settings.mining_schedule));
+            app.manage(throttle::ThrottleState::new(settings.throttle));
+            app.manage(health::HealthState::new(settings.health));
+            autolock::spawn(app.handle().clone());
+            sync::spawn(app.handle().clone());
+            mining_schedule::spawn(app.handle().clone());
+            mining_stats::load(app.handle());
+            mining_stats::spawn(app.handle().clone());
+            throttle::spawn(app.handle().clone(), throttle::Sensors::system());
+            events::spawn(app.handle().clone());
+            health::spawn(app.handle().clone());
+
             let show_i = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
             let hide_i = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
             let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
@@ -557,6 +1012,7 @@ async fn main() {
                         "hide" => {
                             if let Some(w) = app.get_webview_window("main") {
                                 let _ = w.hide();
+                                autolock::window_hidden(app);
                             }
                         }
                         "quit" => {
@@ -576,6 +1032,7 @@ async fn main() {
                         if let Some(w) = app.get_webview_window("main") {
                             if w.is_visible().unwrap_or(false) {
                                 let _ = w.hide();
+                                autolock::window_hidden(app);
                             } else {
                                 let _ = w.show();
                                 let _ = w.set_focus();
@@ -592,9 +1049,16 @@ async fn main() {
             Ok(())
         })
         .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
-                api.prevent_close();
-                let _ = window.hide();
+            match event {
+                WindowEvent::CloseRequested { api, .. } => {
+                    api.prevent_close();
+                    let _ = window.hide();
+                    autolock::window_hidden(window.app_handle());
+                }
+                WindowEvent::Focused(true) => {
+                    autolock::touch(window.app_handle());
+                }
+                _ => {}
             }
         })
         .invoke_handler(tauri::generate_handler![
@@ -606,7 +1070,14 @@ async fn main() {
             stop_daemon,
             reset_blockchain_data,
             save_file,
-            open_file,
+            opener::open_file,
+            opener::open_explorer,
+            confirm::request_confirmation,
+            confirm::reveal_seed,
+            confirm::purge_blockchain_data,
+            reauth::confirm_password,
+            reauth::get_reauth_settings,
+            reauth::set_reauth_settings,
             list_wallets,
             get_active_wallet,
             get_wallet_path_cmd,
@@ -614,8 +1085,71 @@ async fn main() {
             rename_wallet,
             delete_wallet,
             import_wallet_file,
+            import_seed,
+            mnemonic::check_mnemonic,
+            backup::get_backup_status,
+            backup::start_backup_quiz,
+            backup::submit_backup_quiz,
+            sync::get_sync_progress,
+            diagnostics::create_diagnostics_bundle,
+            mining_schedule::get_mining_schedule,
+            mining_schedule::set_mining_schedule,
+            mining_schedule::get_mining_schedule_status,
+            mining_schedule::get_idle_detection,
+            mining_stats::get_mining_stats,
+            mining_stats::get_hashrate_series,
+            mining_stats::export_mining_stats,
+            throttle::get_throttle_settings,
+            throttle::set_throttle_settings,
+            throttle::get_cpu_reading,
+            peers::get_peer_overview,
+            peers::add_preferred_peer,
+            peers::remove_preferred_peer,
+            health::get_network_health,
+            health::get_health_settings,
+            health::set_health_settings,
+            proxy::get_proxy_settings,
+            proxy::set_proxy_settings,
+            proxy::test_proxy,
+            explorer::explorer_block,
+            explorer::explorer_tx,
+            explorer::explorer_mempool,
+            explorer::explorer_search,
+            wallet_cache::get_balance,
+            wallet_cache::get_cached_wallet,
+            reorg::get_confirmation_settings,
+            reorg::set_confirmation_settings,
+            readiness::wait_for_daemon,
             set_tray_unlocked,
+            autolock::report_activity,
+            autolock::get_autolock_settings,
+            autolock::set_autolock_settings,
+            single_instance::frontend_ready,
+            payment_uri::parse_payment_uri,
+            payment_uri::build_payment_uri,
+            payment_uri::get_uri_handler,
+            payment_uri::set_uri_handler,
+            address_book::list_contacts,
+            address_book::add_contact,
+            address_book::update_contact,
+            address_book::delete_contact,
+            address_book::mark_contact_used,
+            address_book::export_contacts,
+            address_book::import_contacts,
+            address_book::migrate_contacts,
+            annotations::send_funds,
+            annotations::get_history,
+            annotations::set_output_label,
+            annotations::set_send_memo,
+            history_export::export_history,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|_app, _event| {
+            // macOS delivers blocknet: links as an open-URL event, not argv
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                single_instance::deliver(_app, urls.into_iter().map(|u| u.to_string()).collect());
+            }
+        });
 }
@@ -0,0 +1,126 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::store;
+
+// Every start_daemon brings up a miner that is stopped and on its default
+// thread count. What the user last chose through the mining controls is
+// kept per wallet and put back once the daemon answers. If that fails before
+// the wallet is unlocked it is tried once more on unlock, then reported.
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiningPreference {
+    pub running: bool,
+    // 0 leaves the daemon's default
+    pub threads: u32,
+}
+
+#[derive(Clone, Serialize)]
+pub struct RestoreFailed {
+    pub wallet: String,
+    pub running: bool,
+    pub threads: u32,
+    pub error: String,
+}
+
+#[derive(Default)]
+pub struct MiningResumeState {
+    pending: Mutex<bool>,
+}
+
+fn pref_path(app: &AppHandle, wallet: &str) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_wallet_data_dir_for(app, wallet)?.join("mining.json"))
+}
+
+// Called by api_call after a mining request from the UI went through
+pub fn remember(app: &AppHandle, path: &str, body: Option<&str>) {
+    let threads = body
+        .and_then(|b| serde_json::from_str::<serde_json::Value>(b).ok())
+        .and_then(|v| v.get("threads").and_then(|t| t.as_u64()))
+        .map(|t| t as u32);
+    let result = crate::get_active_wallet_name(app).and_then(|wallet| {
+        store::update_json(&pref_path(app, &wallet)?, |p: &mut MiningPreference| {
+            match path {
+                "/api/mining/start" => p.running = true,
+                "/api/mining/stop" => p.running = false,
+                "/api/mining/threads" => p.threads = threads.unwrap_or(p.threads),
+                _ => {}
+            }
+            Ok(())
+        })
+    });
+    if let Err(e) = result {
+        eprintln!("Failed to remember mining state: {}", e);
+    }
+}
+
+fn set_pending(app: &AppHandle, pending: bool) -> bool {
+    app.state::<MiningResumeState>()
+        .pending
+        .lock()
+        .map(|mut p| std::mem::replace(&mut *p, pending))
+        .unwrap_or(false)
+}
+
+// Called from start_daemon
+pub fn arm(app: &AppHandle) {
+    set_pending(app, true);
+}
+
+async fn restore(app: &AppHandle, pref: &MiningPreference) -> Result<(), String> {
+    if pref.threads > 0 {
+        let body = serde_json::json!({ "threads": pref.threads });
+        crate::daemon_request(app, "POST", "/api/mining/threads", Some(body.to_string())).await?;
+    }
+    if pref.running {
+        let status: crate::models::MiningStatus = crate::daemon_json(app, "GET", "/api/mining", None).await?;
+        if !status.running {
+            crate::daemon_request(app, "POST", "/api/mining/start", None).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn attempt(app: &AppHandle, last_try: bool) {
+    if !set_pending(app, false) {
+        return;
+    }
+    // An enabled schedule decides for itself after a restart
+    if crate::mining_schedule::enabled(app) {
+        return;
+    }
+    let Ok(wallet) = crate::get_active_wallet_name(app) else { return };
+    let pref: MiningPreference = pref_path(app, &wallet)
+        .and_then(|p| store::load_json(&p))
+        .unwrap_or_default();
+    if !pref.running && pref.threads == 0 {
+        return;
+    }
+    if let Err(error) = restore(app, &pref).await {
+        if !last_try {
+            set_pending(app, true);
+            return;
+        }
+        let _ = app.emit("mining-restore-failed", RestoreFailed {
+            wallet,
+            running: pref.running,
+            threads: pref.threads,
+            error,
+        });
+    }
+}
+
+// Called once the daemon answers /api/status
+pub async fn on_ready(app: &AppHandle) {
+    attempt(app, crate::autolock::is_unlocked(app)).await;
+}
+
+pub fn on_unlocked(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        attempt(&app, true).await;
+    });
+}
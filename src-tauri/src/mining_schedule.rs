@@ -0,0 +1,280 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::models::MiningStatus;
+
+// The scheduler only acts when its decision changes (a window opens, the
+// user walks away, the charger is unplugged), so starting or stopping the
+// miner by hand holds until the next transition. After start_daemon or a
+// settings change the decision is forgotten and applied again from scratch.
+
+const TICK: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiningSchedule {
+    pub enabled: bool,
+    // Local hours; the window wraps past midnight when start > end, and
+    // start == end means all day
+    pub start_hour: u8,
+    pub end_hour: u8,
+    // Mine only once the machine has been idle for idle_after_secs
+    pub idle_only: bool,
+    pub idle_after_secs: u64,
+    pub ac_only: bool,
+    pub threads: u32,
+    // Used while the user is active when idle_only is off
+    pub active_threads: u32,
+}
+
+impl Default for MiningSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_hour: 0,
+            end_hour: 0,
+            idle_only: false,
+            idle_after_secs: 5 * 60,
+            ac_only: true,
+            threads: 2,
+            active_threads: 1,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize)]
+pub struct Decision {
+    pub mine: bool,
+    pub threads: u32,
+    // window | battery | active | idle | scheduled
+    pub reason: &'static str,
+}
+
+pub struct MiningScheduleState {
+    settings: Mutex<MiningSchedule>,
+    applied: Mutex<Option<Decision>>,
+}
+
+impl MiningScheduleState {
+    pub fn new(settings: MiningSchedule) -> Self {
+        Self { settings: Mutex::new(settings), applied: Mutex::new(None) }
+    }
+
+    fn settings(&self) -> MiningSchedule {
+        self.settings.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+fn in_window(hour: u8, start: u8, end: u8) -> bool {
+    match start.cmp(&end) {
+        std::cmp::Ordering::Equal => true,
+        std::cmp::Ordering::Less => hour >= start && hour < end,
+        std::cmp::Ordering::Greater => hour >= start || hour < end,
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let out = crate::background_command(program).args(args).output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+// Best-effort time since the last keyboard or mouse input anywhere on the
+// machine; None when the platform gives no cheap way to ask. Input in our
+// own window says nothing about the rest of the desktop, so there is no
+// fallback: unknown counts as in use.
+fn idle_for() -> Option<Duration> {
+    #[cfg(target_os = "macos")]
+    {
+        let out = command_stdout("ioreg", &["-c", "IOHIDSystem", "-d", "4"])?;
+        let line = out.lines().find(|l| l.contains("\"HIDIdleTime\""))?;
+        let nanos: u64 = line.rsplit('=').next()?.trim().parse().ok()?;
+        Some(Duration::from_nanos(nanos))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let ms: u64 = command_stdout("xprintidle", &[])?.trim().parse().ok()?;
+        Some(Duration::from_millis(ms))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::System::SystemInformation::GetTickCount;
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO { cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32, dwTime: 0 };
+        if unsafe { GetLastInputInfo(&mut info) } == 0 {
+            return None;
+        }
+        // Both are 32-bit millisecond tick counts, wrapping together
+        let ms = unsafe { GetTickCount() }.wrapping_sub(info.dwTime);
+        Some(Duration::from_millis(ms as u64))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+// Machines without a battery count as on AC, as does anything we cannot read
+fn on_ac_power() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else { return true };
+        let read = |p: std::path::PathBuf| std::fs::read_to_string(p).map(|s| s.trim().to_string()).unwrap_or_default();
+        let mut has_mains = false;
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if read(dir.join("type")) == "Mains" {
+                has_mains = true;
+                if read(dir.join("online")) == "1" {
+                    return true;
+                }
+            }
+        }
+        !has_mains
+    }
+    #[cfg(target_os = "macos")]
+    {
+        command_stdout("pmset", &["-g", "batt"])
+            .map(|o| !o.contains("'Battery Power'"))
+            .unwrap_or(true)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+        let mut status = SYSTEM_POWER_STATUS {
+            ACLineStatus: 0,
+            BatteryFlag: 0,
+            BatteryLifePercent: 0,
+            SystemStatusFlag: 0,
+            BatteryLifeTime: 0,
+            BatteryFullLifeTime: 0,
+        };
+        // ACLineStatus is 0 offline, 1 online, 255 unknown
+        let read = unsafe { GetSystemPowerStatus(&mut status) } != 0;
+        !read || status.ACLineStatus != 0
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        true
+    }
+}
+
+fn decide(schedule: &MiningSchedule) -> Decision {
+    use chrono::Timelike;
+    let threads = schedule.threads.max(1);
+    let hour = chrono::Local::now().hour() as u8;
+    if !in_window(hour, schedule.start_hour, schedule.end_hour) {
+        return Decision { mine: false, threads, reason: "window" };
+    }
+    if schedule.ac_only && !on_ac_power() {
+        return Decision { mine: false, threads, reason: "battery" };
+    }
+    let idle = idle_for().is_some_and(|d| d >= Duration::from_secs(schedule.idle_after_secs));
+    if idle {
+        return Decision { mine: true, threads, reason: "idle" };
+    }
+    if schedule.idle_only || schedule.active_threads == 0 {
+        return Decision { mine: false, threads, reason: "active" };
+    }
+    Decision { mine: true, threads: schedule.active_threads.min(threads), reason: "scheduled" }
+}
+
+async fn apply(app: &AppHandle, decision: &Decision) -> Result<(), String> {
+    let status: MiningStatus = crate::daemon_json(app, "GET", "/api/mining", None).await?;
+    if decision.mine && status.threads != decision.threads {
+        let body = serde_json::json!({ "threads": decision.threads });
+        crate::daemon_request(app, "POST", "/api/mining/threads", Some(body.to_string())).await?;
+    }
+    if decision.mine && !status.running {
+        crate::daemon_request(app, "POST", "/api/mining/start", None).await?;
+    } else if !decision.mine && status.running {
+        crate::daemon_request(app, "POST", "/api/mining/stop", None).await?;
+    }
+    Ok(())
+}
+
+async fn tick(app: &AppHandle) {
+    let state = app.state::<MiningScheduleState>();
+    let schedule = state.settings();
+    if !schedule.enabled {
+        return;
+    }
+    let decision = decide(&schedule);
+    let unchanged = state.applied.lock().map(|a| a.as_ref() == Some(&decision)).unwrap_or(false);
+    if unchanged {
+        return;
+    }
+    // Left unrecorded on failure so the next tick tries again, which also
+    // covers the daemon still starting up
+    if apply(app, &decision).await.is_ok() {
+        if let Ok(mut applied) = state.applied.lock() {
+            *applied = Some(decision.clone());
+        }
+        let _ = app.emit("mining-schedule", decision);
+    }
+}
+
+pub fn enabled(app: &AppHandle) -> bool {
+    app.state::<MiningScheduleState>().settings().enabled
+}
+
+// Called after start_daemon: a fresh daemon starts with mining off
+pub fn reapply(app: &AppHandle) {
+    if let Ok(mut applied) = app.state::<MiningScheduleState>().applied.lock() {
+        *applied = None;
+    }
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tick(&app).await;
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_mining_schedule(state: State<'_, MiningScheduleState>) -> MiningSchedule {
+    state.settings()
+}
+
+#[tauri::command]
+pub async fn set_mining_schedule(
+    app: AppHandle,
+    state: State<'_, MiningScheduleState>,
+    schedule: MiningSchedule,
+) -> Result<(), String> {
+    if schedule.start_hour > 23 || schedule.end_hour > 23 {
+        return Err("Hours must be between 0 and 23".to_string());
+    }
+    crate::settings::update(&app, |s| s.mining_schedule = schedule.clone())?;
+    *state.settings.lock().map_err(|e| format!("Lock error: {}", e))? = schedule;
+    reapply(&app);
+    tick(&app).await;
+    Ok(())
+}
+
+// What the scheduler last applied; None while disabled or not yet applied
+#[tauri::command]
+pub fn get_mining_schedule_status(state: State<'_, MiningScheduleState>) -> Option<Decision> {
+    if !state.settings().enabled {
+        return None;
+    }
+    state.applied.lock().ok().and_then(|a| a.clone())
+}
+
+// False when idle_for has no way to ask, so the settings can say why an
+// idle-only schedule never mines
+#[tauri::command]
+pub async fn get_idle_detection() -> bool {
+    idle_for().is_some()
+}
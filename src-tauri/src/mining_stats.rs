@@ -0,0 +1,341 @@
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::address::format_bnt;
+use crate::models::{BlockHeader, HistoryResponse, MiningStatus};
+use crate::{csv, store};
+
+// /api/mining only knows the current session, so it is sampled once a minute
+// into mining-stats.json: minute samples for the last day, hourly rollups for
+// a year, and every mined_block event. Earnings come from the wallet's own
+// coinbase outputs; events the wallet never received are reported alongside
+// so orphaned blocks stand out.
+
+const TICK: Duration = Duration::from_secs(60);
+const SAVE_EVERY: u32 = 5;
+const RECENT_SECS: i64 = 24 * 3600;
+const HOURLY_SECS: i64 = 365 * 24 * 3600;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Sample {
+    pub at: i64,
+    pub hashrate: f64,
+    pub threads: u32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HourBucket {
+    // Unix time of the start of the hour
+    pub hour: i64,
+    pub samples: u32,
+    pub hashrate_sum: f64,
+    pub hashes: u64,
+    pub mining_secs: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MinedBlock {
+    pub height: u64,
+    pub hash: String,
+    pub reward: u64,
+    pub at: i64,
+    pub wallet: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct StatsFile {
+    recent: VecDeque<Sample>,
+    hourly: VecDeque<HourBucket>,
+    mined: Vec<MinedBlock>,
+    // Block timestamps already looked up for coinbase outputs
+    block_times: BTreeMap<u64, i64>,
+}
+
+#[derive(Default)]
+struct Tracker {
+    file: StatsFile,
+    last_hash_count: Option<u64>,
+    dirty_ticks: u32,
+}
+
+#[derive(Default)]
+pub struct MiningStatsState {
+    tracker: Mutex<Tracker>,
+}
+
+#[derive(Clone, Default, Serialize)]
+pub struct DayStats {
+    // YYYY-MM-DD, UTC
+    pub date: String,
+    pub earned: u64,
+    pub earned_bnt: String,
+    // Coinbase outputs the wallet received
+    pub blocks: u32,
+    // mined_block events seen that day, whether or not they paid out
+    pub reported: u32,
+    pub avg_hashrate: f64,
+    pub mining_secs: u64,
+}
+
+#[derive(Serialize)]
+pub struct HashratePoint {
+    pub at: i64,
+    pub hashrate: f64,
+}
+
+#[derive(Serialize)]
+pub struct MiningStats {
+    pub days: Vec<DayStats>,
+    pub total_earned: u64,
+    pub total_earned_bnt: String,
+    // Reported by the daemon but not (yet) in the wallet: orphaned, or
+    // still waiting for a rescan
+    pub unmatched: Vec<MinedBlock>,
+}
+
+fn stats_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_app_dir(app)?.join("mining-stats.json"))
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn day_of(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, tracker: &mut Tracker) {
+    tracker.dirty_ticks = 0;
+    if let Err(e) = stats_path(app).and_then(|p| store::save_json(&p, &tracker.file)) {
+        eprintln!("Failed to save mining stats: {}", e);
+    }
+}
+
+pub fn load(app: &AppHandle) {
+    let file = stats_path(app).and_then(|p| store::load_json(&p)).unwrap_or_default();
+    if let Ok(mut tracker) = app.state::<MiningStatsState>().tracker.lock() {
+        tracker.file = file;
+    }
+}
+
+fn record(tracker: &mut Tracker, status: &MiningStatus, now: i64) {
+    // hash_count restarts with every mining session
+    let hashes = match tracker.last_hash_count {
+        Some(last) if status.hash_count >= last => status.hash_count - last,
+        _ => status.hash_count,
+    };
+    tracker.last_hash_count = if status.running { Some(status.hash_count) } else { None };
+    if !status.running {
+        return;
+    }
+
+    let file = &mut tracker.file;
+    file.recent.push_back(Sample { at: now, hashrate: status.hashrate, threads: status.threads });
+    while file.recent.front().is_some_and(|s| s.at < now - RECENT_SECS) {
+        file.recent.pop_front();
+    }
+
+    let hour = now - now.rem_euclid(3600);
+    if file.hourly.back().is_none_or(|b| b.hour != hour) {
+        file.hourly.push_back(HourBucket { hour, ..Default::default() });
+    }
+    if let Some(bucket) = file.hourly.back_mut() {
+        bucket.samples += 1;
+        bucket.hashrate_sum += status.hashrate;
+        bucket.hashes += hashes;
+        bucket.mining_secs += TICK.as_secs();
+    }
+    while file.hourly.front().is_some_and(|b| b.hour < now - HOURLY_SECS) {
+        file.hourly.pop_front();
+    }
+}
+
+async fn sample(app: &AppHandle) -> Result<(), String> {
+    let status: MiningStatus = crate::daemon_json(app, "GET", "/api/mining", None).await?;
+    let state = app.state::<MiningStatsState>();
+    let mut tracker = state.tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    record(&mut tracker, &status, now_secs());
+    tracker.dirty_ticks += 1;
+    if tracker.dirty_ticks >= SAVE_EVERY {
+        save(app, &mut tracker);
+    }
+    Ok(())
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            if sample(&app).await.is_err() {
+                // Daemon down; the next session starts its own count
+                if let Ok(mut tracker) = app.state::<MiningStatsState>().tracker.lock() {
+                    tracker.last_hash_count = None;
+                }
+            }
+        }
+    });
+}
+
+#[derive(Deserialize)]
+struct MinedEvent {
+    height: u64,
+    hash: String,
+    #[serde(default)]
+    reward: u64,
+}
+
+// Called from events for every mined_block on the daemon's event stream
+pub fn on_mined_block(app: &AppHandle, data: &str) {
+    let Ok(event) = serde_json::from_str::<MinedEvent>(data) else { return };
+    let wallet = crate::get_active_wallet_name(app).unwrap_or_default();
+    let state = app.state::<MiningStatsState>();
+    let Ok(mut tracker) = state.tracker.lock() else { return };
+    if tracker.file.mined.iter().any(|m| m.height == event.height && m.hash == event.hash) {
+        return;
+    }
+    tracker.file.mined.push(MinedBlock {
+        height: event.height,
+        hash: event.hash,
+        reward: event.reward,
+        at: now_secs(),
+        wallet,
+    });
+    save(app, &mut tracker);
+}
+
+// Coinbase outputs of the active wallet as (height, amount, timestamp)
+async fn coinbase_outputs(app: &AppHandle) -> Result<Vec<(u64, u64, i64)>, String> {
+    let history: HistoryResponse = crate::daemon_json(app, "GET", "/api/wallet/history", None).await?;
+    let state = app.state::<MiningStatsState>();
+    let known = state.tracker.lock().map_err(|e| format!("Lock error: {}", e))?.file.block_times.clone();
+
+    let mut fetched = BTreeMap::new();
+    let mut out = Vec::new();
+    for o in history.outputs.iter().filter(|o| o.is_coinbase) {
+        let ts = match known.get(&o.block_height).or(fetched.get(&o.block_height)) {
+            Some(ts) => *ts,
+            None => {
+                let path = format!("/api/block/{}", o.block_height);
+                let ts = crate::daemon_json::<BlockHeader>(app, "GET", &path, None).await?.timestamp;
+                fetched.insert(o.block_height, ts);
+                ts
+            }
+        };
+        out.push((o.block_height, o.amount, ts));
+    }
+
+    if !fetched.is_empty() {
+        let mut tracker = state.tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+        tracker.file.block_times.extend(fetched);
+        save(app, &mut tracker);
+    }
+    Ok(out)
+}
+
+fn day_entry(by_day: &mut BTreeMap<String, (DayStats, f64, u32)>, ts: i64) -> &mut (DayStats, f64, u32) {
+    let date = day_of(ts);
+    by_day
+        .entry(date.clone())
+        .or_insert_with(|| (DayStats { date, ..Default::default() }, 0.0, 0))
+}
+
+async fn collect(app: &AppHandle, days: Option<u32>) -> Result<MiningStats, String> {
+    let wallet = crate::get_active_wallet_name(app)?;
+    let coinbase = coinbase_outputs(app).await?;
+    let (hourly, mined) = {
+        let state = app.state::<MiningStatsState>();
+        let tracker = state.tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mined: Vec<MinedBlock> = tracker.file.mined.iter().filter(|m| m.wallet == wallet).cloned().collect();
+        (tracker.file.hourly.clone(), mined)
+    };
+    let since = days.map(|d| now_secs() - d as i64 * 86400).unwrap_or(i64::MIN);
+
+    // Per day: stats plus the running hashrate sum and sample count
+    let mut by_day: BTreeMap<String, (DayStats, f64, u32)> = BTreeMap::new();
+    let mut paid_heights = HashSet::new();
+    for (height, amount, ts) in coinbase.iter().filter(|(_, _, ts)| *ts >= since) {
+        let (day, _, _) = day_entry(&mut by_day, *ts);
+        day.earned += amount;
+        if paid_heights.insert(*height) {
+            day.blocks += 1;
+        }
+    }
+    for m in mined.iter().filter(|m| m.at >= since) {
+        day_entry(&mut by_day, m.at).0.reported += 1;
+    }
+    for b in hourly.iter().filter(|b| b.hour >= since) {
+        let (day, sum, samples) = day_entry(&mut by_day, b.hour);
+        day.mining_secs += b.mining_secs;
+        *sum += b.hashrate_sum;
+        *samples += b.samples;
+    }
+
+    let all_paid: HashSet<u64> = coinbase.iter().map(|(h, _, _)| *h).collect();
+    let unmatched = mined.into_iter().filter(|m| !all_paid.contains(&m.height)).collect();
+    let days: Vec<DayStats> = by_day
+        .into_values()
+        .map(|(mut day, sum, samples)| {
+            day.avg_hashrate = if samples > 0 { sum / samples as f64 } else { 0.0 };
+            day.earned_bnt = format_bnt(day.earned);
+            day
+        })
+        .collect();
+    let total_earned = days.iter().map(|d| d.earned).sum();
+    Ok(MiningStats { days, total_earned, total_earned_bnt: format_bnt(total_earned), unmatched })
+}
+
+#[tauri::command]
+pub async fn get_mining_stats(app: AppHandle, days: Option<u32>) -> Result<MiningStats, String> {
+    collect(&app, days).await
+}
+
+// Minute resolution up to a day back, hourly averages beyond that
+#[tauri::command]
+pub fn get_hashrate_series(state: State<'_, MiningStatsState>, hours: u32) -> Result<Vec<HashratePoint>, String> {
+    let tracker = state.tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let since = now_secs() - hours as i64 * 3600;
+    let points = if hours as i64 * 3600 <= RECENT_SECS {
+        tracker.file.recent
+            .iter()
+            .filter(|s| s.at >= since)
+            .map(|s| HashratePoint { at: s.at, hashrate: s.hashrate })
+            .collect()
+    } else {
+        tracker.file.hourly
+            .iter()
+            .filter(|b| b.hour >= since && b.samples > 0)
+            .map(|b| HashratePoint { at: b.hour, hashrate: b.hashrate_sum / b.samples as f64 })
+            .collect()
+    };
+    Ok(points)
+}
+
+#[tauri::command]
+pub async fn export_mining_stats(app: AppHandle, days: Option<u32>) -> Result<Option<String>, String> {
+    let stats = collect(&app, days).await?;
+    let mut out = csv::row(&["date", "blocks", "earned_bnt", "reported_blocks", "avg_hashrate", "mining_hours"]);
+    out.push('\n');
+    for d in &stats.days {
+        out.push_str(&csv::row(&[
+            d.date.clone(),
+            d.blocks.to_string(),
+            d.earned_bnt.clone(),
+            d.reported.to_string(),
+            format!("{:.2}", d.avg_hashrate),
+            format!("{:.2}", d.mining_secs as f64 / 3600.0),
+        ]));
+        out.push('\n');
+    }
+    let filename = format!("blocknet-mining-{}.csv", chrono::Local::now().format("%Y%m%d%H%M%S"));
+    let path = crate::save_bytes(&app, &filename, out.as_bytes(), true).await?;
+    Ok(path.map(|p| p.to_string_lossy().to_string()))
+}
@@ -0,0 +1,193 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+// BIP39 recovery phrases as the daemon expects them: 12 words from the
+// English list, 128 bits of entropy plus a 4-bit SHA-256 checksum.
+
+const WORDLIST: &str = include_str!("bip39_english.txt");
+pub const WORDS: usize = 12;
+const MAX_SUGGESTIONS: usize = 3;
+
+fn wordlist() -> &'static [&'static str] {
+    static LIST: OnceLock<Vec<&'static str>> = OnceLock::new();
+    LIST.get_or_init(|| WORDLIST.lines().collect())
+}
+
+fn index_of(word: &str) -> Option<usize> {
+    wordlist().binary_search(&word).ok()
+}
+
+#[derive(Serialize)]
+pub struct WordCheck {
+    pub position: usize,
+    pub word: String,
+    pub known: bool,
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct MnemonicCheck {
+    pub valid: bool,
+    pub words: Vec<WordCheck>,
+    pub error: Option<String>,
+}
+
+pub fn split(phrase: &str) -> Vec<String> {
+    phrase.split_whitespace().map(|w| w.to_lowercase()).collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+// BIP39 words are unique in their first four letters, so a matching prefix
+// is a confident answer; otherwise the closest words by edit distance.
+pub fn suggest(word: &str) -> Vec<String> {
+    if word.chars().count() >= 4 {
+        let prefix: String = word.chars().take(4).collect();
+        if let Some(w) = wordlist().iter().find(|w| w.starts_with(&prefix)) {
+            return vec![w.to_string()];
+        }
+    }
+    let max = if word.chars().count() <= 4 { 1 } else { 2 };
+    let mut close: Vec<(usize, &str)> = wordlist()
+        .iter()
+        .map(|w| (edit_distance(word, w), *w))
+        .filter(|(d, _)| *d <= max)
+        .collect();
+    close.sort();
+    close.into_iter().take(MAX_SUGGESTIONS).map(|(_, w)| w.to_string()).collect()
+}
+
+fn checksum_ok(indices: &[usize]) -> bool {
+    // 12 x 11 bits = 128 bits of entropy followed by 4 bits of checksum
+    let mut bits: u128 = 0;
+    let mut checksum: u8 = 0;
+    for (i, &idx) in indices.iter().enumerate() {
+        if i + 1 < indices.len() {
+            bits = (bits << 11) | idx as u128;
+        } else {
+            bits = (bits << 7) | (idx >> 4) as u128;
+            checksum = (idx & 0x0f) as u8;
+        }
+    }
+    let hash = Sha256::digest(bits.to_be_bytes());
+    hash[0] >> 4 == checksum
+}
+
+pub fn check(phrase: &str) -> MnemonicCheck {
+    let words: Vec<WordCheck> = split(phrase)
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let known = index_of(&word).is_some();
+            WordCheck {
+                position: i + 1,
+                suggestions: if known { Vec::new() } else { suggest(&word) },
+                known,
+                word,
+            }
+        })
+        .collect();
+
+    let error = if words.len() != WORDS {
+        Some(format!("Recovery phrase must be exactly {} words, got {}", WORDS, words.len()))
+    } else if let Some(w) = words.iter().find(|w| !w.known) {
+        Some(match w.suggestions.as_slice() {
+            [] => format!("Word {} \"{}\" is not in the BIP39 word list", w.position, w.word),
+            s => format!("Word {} \"{}\" is not in the BIP39 word list. Did you mean {}?", w.position, w.word, s.join(", ")),
+        })
+    } else {
+        let indices: Vec<usize> = words.iter().filter_map(|w| index_of(&w.word)).collect();
+        if checksum_ok(&indices) {
+            None
+        } else {
+            Some("Checksum mismatch: the words are valid but one is wrong or out of order".to_string())
+        }
+    };
+    MnemonicCheck { valid: error.is_none(), words, error }
+}
+
+// The phrase normalised to single-spaced lowercase, or why it is not usable
+pub fn validate(phrase: &str) -> Result<String, String> {
+    let result = check(phrase);
+    match result.error {
+        Some(e) => Err(e),
+        None => Ok(split(phrase).join(" ")),
+    }
+}
+
+#[tauri::command]
+pub fn check_mnemonic(phrase: String) -> MnemonicCheck {
+    check(&phrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP39 reference vectors (entropy 00.., 7f.., ff..)
+    const VALID: [&str; 3] = [
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+    ];
+
+    #[test]
+    fn accepts_reference_phrases() {
+        for phrase in VALID {
+            let c = check(phrase);
+            assert!(c.valid, "{}: {:?}", phrase, c.error);
+            assert_eq!(c.words.len(), WORDS);
+        }
+        // Case and spacing are not part of the phrase
+        assert!(check("  ABANDON abandon abandon abandon abandon abandon\nabandon abandon abandon abandon abandon About ").valid);
+    }
+
+    #[test]
+    fn rejects_bad_phrases() {
+        let cases = [
+            ("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon", "Checksum mismatch"),
+            ("about abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon", "Checksum mismatch"),
+            ("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "exactly 12 words, got 11"),
+            ("", "exactly 12 words, got 0"),
+            ("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abuot", "Word 12 \"abuot\""),
+        ];
+        for (phrase, want) in cases {
+            let c = check(phrase);
+            assert!(!c.valid, "{}", phrase);
+            let error = c.error.unwrap_or_default();
+            assert!(error.contains(want), "{}: {}", phrase, error);
+        }
+    }
+
+    #[test]
+    fn suggests_fixes_for_typos() {
+        let cases: &[(&str, &[&str])] = &[
+            ("abou", &["about"]),
+            ("abandonn", &["abandon"]),
+            ("walnt", &["walnut"]),
+            ("zooo", &["zoo"]),
+            ("alrt", &["alert", "art"]),
+            ("xyzzyq", &[]),
+        ];
+        for (word, want) in cases {
+            assert_eq!(suggest(word), *want, "{}", word);
+        }
+        let c = check("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abuot");
+        assert_eq!(c.words[11].suggestions, ["about", "abuse", "adult"]);
+        assert!(c.words[..11].iter().all(|w| w.known && w.suggestions.is_empty()));
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+// Typed views of daemon responses the backend reads itself. Field docs live
+// in api_openapi.json; only what we use is modelled, and everything the
+// daemon may omit is defaulted.
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OutputEntry {
+    pub txid: String,
+    pub output_index: u32,
+    pub amount: u64,
+    pub block_height: u64,
+    #[serde(default)]
+    pub is_coinbase: bool,
+    #[serde(default)]
+    pub spent: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spent_height: Option<u64>,
+}
+
+#[derive(Default, Deserialize)]
+pub struct HistoryResponse {
+    #[serde(default)]
+    pub outputs: Vec<OutputEntry>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Balance {
+    pub spendable: u64,
+    pub pending: u64,
+    pub total: u64,
+    pub outputs_total: u64,
+    pub outputs_unspent: u64,
+    pub chain_height: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SendResponse {
+    pub txid: String,
+    #[serde(default)]
+    pub fee: u64,
+    #[serde(default)]
+    pub change: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonStats {
+    pub peers: u32,
+    pub chain_height: u64,
+    pub best_hash: String,
+    pub mempool_size: u64,
+    pub syncing: bool,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BlockHeader {
+    pub height: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiningStatus {
+    pub running: bool,
+    pub threads: u32,
+    pub hashrate: f64,
+    pub hash_count: u64,
+    pub blocks_found: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeerList {
+    pub count: u32,
+    pub peers: Vec<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BanEntry {
+    pub peer_id: String,
+    pub reason: String,
+    pub ban_count: u32,
+    pub permanent: bool,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BannedList {
+    pub count: u32,
+    pub banned: Vec<BanEntry>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TxSummary {
+    pub hash: String,
+    pub is_coinbase: bool,
+    pub inputs: u32,
+    pub outputs: u32,
+    pub fee: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Block {
+    pub height: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    pub merkle_root: String,
+    pub timestamp: i64,
+    pub difficulty: u64,
+    pub nonce: u64,
+    pub tx_count: u32,
+    pub transactions: Vec<TxSummary>,
+    pub reward: u64,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TxLookup {
+    pub tx: serde_json::Value,
+    pub block_height: Option<u64>,
+    pub in_mempool: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MempoolStats {
+    pub count: u64,
+    pub size_bytes: u64,
+    pub min_fee: u64,
+    pub max_fee: u64,
+    pub avg_fee: f64,
+}
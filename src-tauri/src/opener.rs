@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+// Files the app wrote on the user's behalf this session (downloads, exports,
+// backups). open_file only accepts these, so the webview cannot use it to
+// launch arbitrary programs, documents or URLs.
+
+const EXPLORER_URL: &str = "https://explorer.blocknetcrypto.com";
+
+#[derive(Default)]
+pub struct OpenablePaths {
+    paths: Mutex<HashSet<PathBuf>>,
+}
+
+fn canonical(path: &Path) -> Option<PathBuf> {
+    std::fs::canonicalize(path).ok()
+}
+
+pub fn remember(app: &AppHandle, path: &Path) {
+    let Some(path) = canonical(path) else { return };
+    if let Ok(mut paths) = app.state::<OpenablePaths>().paths.lock() {
+        paths.insert(path);
+    }
+}
+
+fn is_known(app: &AppHandle, path: &Path) -> bool {
+    let Some(path) = canonical(path) else { return false };
+    app.state::<OpenablePaths>()
+        .paths
+        .lock()
+        .map(|paths| paths.contains(&path))
+        .unwrap_or(false)
+}
+
+fn system_open(target: &std::ffi::OsStr) -> Result<(), String> {
+    let cmd = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(cmd)
+        .arg(target)
+        .spawn()
+        .map_err(|e| format!("Failed to open: {}", e))?;
+    Ok(())
+}
+
+// Shows the file selected in the file manager where the platform supports
+// it; on Linux there is no portable way, so the containing folder opens.
+fn reveal(path: &Path) -> Result<(), String> {
+    let result = if cfg!(target_os = "macos") {
+        std::process::Command::new("open").arg("-R").arg(path).spawn()
+    } else if cfg!(target_os = "windows") {
+        let mut arg = std::ffi::OsString::from("/select,");
+        arg.push(path);
+        std::process::Command::new("explorer").arg(arg).spawn()
+    } else {
+        let dir = path.parent().unwrap_or(path);
+        std::process::Command::new("xdg-open").arg(dir).spawn()
+    };
+    result.map(|_| ()).map_err(|e| format!("Failed to reveal file: {}", e))
+}
+
+#[tauri::command]
+pub async fn open_file(app: AppHandle, path: String, reveal_in_folder: Option<bool>) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    if !is_known(&app, &path) {
+        return Err("Only files saved by this app can be opened".to_string());
+    }
+    if reveal_in_folder.unwrap_or(false) {
+        reveal(&path)
+    } else {
+        system_open(path.as_os_str())
+    }
+}
+
+// Opens a transaction or block on the public explorer, or on the one the
+// proxy settings allow. The URL is built here from a kind and an id rather
+// than accepted from the webview.
+#[tauri::command]
+pub async fn open_explorer(app: AppHandle, kind: String, id: String) -> Result<(), String> {
+    let segment = match kind.as_str() {
+        "tx" => "tx",
+        "block" => "block",
+        _ => return Err(format!("Unsupported explorer link: {}", kind)),
+    };
+    let id = id.trim();
+    if id.is_empty() || id.len() > 128 || !id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err("Invalid explorer id".to_string());
+    }
+    let base = crate::proxy::explorer_base(&app, EXPLORER_URL)?;
+    system_open(format!("{}/{}/{}", base, segment, id).as_ref())
+}
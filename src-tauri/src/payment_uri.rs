@@ -0,0 +1,209 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::address;
+
+// blocknet:<address>?amount=<BNT>&label=<text>&message=<text>
+// Modelled on BIP21: amount is in whole BNT with up to 8 decimals, unknown
+// parameters are ignored unless prefixed with req- (which we must refuse).
+
+pub const SCHEME: &str = "blocknet";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PaymentRequest {
+    pub address: String,
+    // Atomic units, ready for SendRequest.amount
+    pub amount: Option<u64>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+pub fn parse(uri: &str) -> Result<PaymentRequest, String> {
+    let url = url::Url::parse(uri.trim()).map_err(|e| format!("Invalid payment URI: {}", e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {} URI", SCHEME));
+    }
+    // Accept both blocknet:ADDR and blocknet://ADDR
+    let address = match url.host_str() {
+        Some(host) => host.to_string(),
+        None => url.path().trim_matches('/').to_string(),
+    };
+    address::validate(&address)?;
+
+    let mut req = PaymentRequest { address, amount: None, label: None, message: None };
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "amount" => {
+                let amount = address::parse_bnt(&value)?;
+                if amount == 0 {
+                    return Err("Amount must be greater than zero".to_string());
+                }
+                req.amount = Some(amount);
+            }
+            "label" => req.label = Some(value.into_owned()),
+            "message" => req.message = Some(value.into_owned()),
+            k if k.starts_with("req-") => {
+                return Err(format!("Unsupported required parameter: {}", k));
+            }
+            _ => {}
+        }
+    }
+    Ok(req)
+}
+
+pub fn build(req: &PaymentRequest) -> Result<String, String> {
+    address::validate(&req.address)?;
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(amount) = req.amount {
+        query.append_pair("amount", &address::format_bnt(amount));
+    }
+    if let Some(label) = req.label.as_deref().filter(|s| !s.is_empty()) {
+        query.append_pair("label", label);
+    }
+    if let Some(message) = req.message.as_deref().filter(|s| !s.is_empty()) {
+        query.append_pair("message", message);
+    }
+    let query = query.finish();
+    if query.is_empty() {
+        Ok(format!("{}:{}", SCHEME, req.address))
+    } else {
+        Ok(format!("{}:{}?{}", SCHEME, req.address, query))
+    }
+}
+
+pub fn is_payment_uri(arg: &str) -> bool {
+    arg.get(..SCHEME.len()).is_some_and(|s| s.eq_ignore_ascii_case(SCHEME))
+        && arg.as_bytes().get(SCHEME.len()) == Some(&b':')
+}
+
+// Parses and hands a URI to the send view; invalid ones are reported rather
+// than silently dropped so the user knows why nothing was filled in.
+pub fn dispatch(app: &AppHandle, uri: &str) {
+    match parse(uri) {
+        Ok(req) => {
+            let _ = app.emit("payment-request", req);
+        }
+        Err(e) => {
+            let _ = app.emit("payment-request-error", e);
+        }
+    }
+}
+
+// Installers register blocknet: from the deep-link config in
+// tauri.conf.json. Whether links reach this executable; None where the OS
+// ties the scheme to the installed bundle (macOS) and there is nothing to
+// check or change.
+#[tauri::command]
+pub fn get_uri_handler(app: AppHandle) -> Option<bool> {
+    app.deep_link().is_registered(SCHEME).ok()
+}
+
+// For copies that were not installed (an AppImage, a dev build). Replaces
+// whatever handler the user had picked, so it only runs when they ask.
+#[tauri::command]
+pub fn set_uri_handler(app: AppHandle) -> Result<(), String> {
+    app.deep_link()
+        .register(SCHEME)
+        .map_err(|e| format!("Failed to register blocknet: links: {}", e))
+}
+
+#[tauri::command]
+pub fn parse_payment_uri(uri: String) -> Result<PaymentRequest, String> {
+    parse(&uri)
+}
+
+#[tauri::command]
+pub fn build_payment_uri(
+    address: String,
+    amount: Option<u64>,
+    label: Option<String>,
+    message: Option<String>,
+) -> Result<String, String> {
+    build(&PaymentRequest { address, amount, label, message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "2Ana1pUpv2ZbMVkwF5FXapYeBEjdxDatLn7nvJkhgTSXbs59SyZSx866bXirPgj8QQVB57uxHJBG1YFvkRbFj4T";
+
+    fn uri(query: &str) -> String {
+        format!("{}:{}{}", SCHEME, ADDRESS, query)
+    }
+
+    #[test]
+    fn parses_amounts() {
+        let cases: &[(&str, Result<Option<u64>, &str>)] = &[
+            ("", Ok(None)),
+            ("?amount=1.5", Ok(Some(150_000_000))),
+            ("?amount=0.00000001", Ok(Some(1))),
+            ("?amount=0.000000001", Err("more than 8 decimal places")),
+            ("?amount=1.123456789", Err("more than 8 decimal places")),
+            ("?amount=0", Err("greater than zero")),
+            ("?amount=abc", Err("Invalid amount")),
+        ];
+        for (query, want) in cases {
+            let got = parse(&uri(query)).map(|r| r.amount);
+            match (got, want) {
+                (Ok(got), Ok(want)) => assert_eq!(got, *want, "{}", query),
+                (Err(e), Err(want)) => assert!(e.contains(want), "{}: {}", query, e),
+                (got, want) => panic!("{}: got {:?}, want {:?}", query, got, want),
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_percent_encoded_text() {
+        let req = parse(&uri("?label=Caf%C3%A9%20%26%20Co&message=rent+for%3F%20May")).unwrap();
+        assert_eq!(req.label.as_deref(), Some("Café & Co"));
+        assert_eq!(req.message.as_deref(), Some("rent for? May"));
+    }
+
+    #[test]
+    fn accepts_both_address_forms() {
+        assert_eq!(parse(&uri("")).unwrap().address, ADDRESS);
+        let slashed = format!("{}://{}?amount=1", SCHEME, ADDRESS);
+        assert_eq!(parse(&slashed).unwrap().address, ADDRESS);
+    }
+
+    #[test]
+    fn refuses_foreign_and_required_params() {
+        assert!(parse(&format!("bitcoin:{}", ADDRESS)).is_err());
+        assert!(parse("blocknet:notanaddress").is_err());
+        assert!(parse(&uri("?req-expires=1")).unwrap_err().contains("req-expires"));
+        // Unknown optional parameters are ignored
+        assert!(parse(&uri("?foo=bar")).is_ok());
+    }
+
+    #[test]
+    fn build_round_trips_through_parse() {
+        let cases = [
+            (None, None, None),
+            (Some(1), None, None),
+            (Some(123_456_789), Some("Café & Co"), Some("a=b?c #1 100%")),
+            (None, Some("line\nbreak"), None),
+        ];
+        for (amount, label, message) in cases {
+            let req = PaymentRequest {
+                address: ADDRESS.to_string(),
+                amount,
+                label: label.map(str::to_string),
+                message: message.map(str::to_string),
+            };
+            let built = build(&req).unwrap();
+            let back = parse(&built).unwrap();
+            assert_eq!(back.address, req.address, "{}", built);
+            assert_eq!(back.amount, req.amount, "{}", built);
+            assert_eq!(back.label, req.label, "{}", built);
+            assert_eq!(back.message, req.message, "{}", built);
+        }
+        assert_eq!(build(&PaymentRequest {
+            address: ADDRESS.to_string(),
+            amount: None,
+            label: Some(String::new()),
+            message: None,
+        }).unwrap(), uri(""));
+    }
+}
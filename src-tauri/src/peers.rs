@@ -0,0 +1,145 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::models::{BanEntry, BannedList, PeerList};
+use crate::store;
+
+// Peer control beyond what /api/peers shows. The daemon API can only list
+// peers and bans, so there is no connecting, disconnecting or banning on the
+// fly: preferred peers are kept in peers.json and handed to the daemon as
+// --seed on every start_daemon. A daemon that rejects the flag is started
+// again without it (see start_daemon).
+
+pub const SEED_FLAG: &str = "--seed";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PreferredPeer {
+    pub multiaddr: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub added_at: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PeerConfig {
+    preferred: Vec<PreferredPeer>,
+}
+
+#[derive(Serialize)]
+pub struct PeerOverview {
+    pub connected: Vec<String>,
+    pub banned: Vec<BanEntry>,
+    pub preferred: Vec<PreferredPeer>,
+}
+
+fn config_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_app_dir(app)?.join("peers.json"))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn valid_peer_id(id: &str) -> bool {
+    (id.starts_with("12D3KooW") || id.starts_with("Qm"))
+        && id.len() >= 46
+        && id.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+// Accepts the transport addresses libp2p dials in practice, ending in
+// /p2p/<peer id> so the daemon knows whom it is talking to
+fn check_multiaddr(addr: &str) -> Result<(), String> {
+    let invalid = |why: &str| Err(format!("Invalid multiaddr {}: {}", addr, why));
+    let Some(rest) = addr.strip_prefix('/') else { return invalid("must start with /") };
+    let parts: Vec<&str> = rest.split('/').collect();
+    let mut i = 0;
+    let mut has_host = false;
+    while i < parts.len() {
+        let proto = parts[i];
+        let value = parts.get(i + 1).copied().unwrap_or("");
+        let ok = match proto {
+            "ip4" => value.parse::<Ipv4Addr>().is_ok(),
+            "ip6" => value.parse::<Ipv6Addr>().is_ok(),
+            "dns" | "dns4" | "dns6" => {
+                !value.is_empty() && value.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.')
+            }
+            "tcp" | "udp" => value.parse::<u16>().is_ok_and(|p| p > 0),
+            "p2p" => valid_peer_id(value),
+            "quic" | "quic-v1" | "ws" | "wss" | "tls" => {
+                i += 1;
+                continue;
+            }
+            _ => return invalid(&format!("unsupported protocol '{}'", proto)),
+        };
+        if !ok {
+            return invalid(&format!("bad value for /{}", proto));
+        }
+        if matches!(proto, "ip4" | "ip6" | "dns" | "dns4" | "dns6") {
+            has_host = true;
+        }
+        i += 2;
+    }
+    if !has_host {
+        return invalid("missing host");
+    }
+    if parts.len() < 2 || parts[parts.len() - 2] != "p2p" {
+        return invalid("must end with /p2p/<peer id>");
+    }
+    Ok(())
+}
+
+// Extra start_daemon arguments for the preferred peers
+pub fn startup_args(app: &AppHandle) -> Vec<String> {
+    let config: PeerConfig = config_path(app).and_then(|p| store::load_json(&p)).unwrap_or_default();
+    let addrs: Vec<&str> = config.preferred
+        .iter()
+        .map(|p| p.multiaddr.as_str())
+        .filter(|a| check_multiaddr(a).is_ok())
+        .collect();
+    if addrs.is_empty() {
+        return Vec::new();
+    }
+    vec![SEED_FLAG.to_string(), addrs.join(",")]
+}
+
+#[tauri::command]
+pub async fn get_peer_overview(app: AppHandle) -> Result<PeerOverview, String> {
+    let peers: PeerList = crate::daemon_json(&app, "GET", "/api/peers", None).await?;
+    let banned: BannedList = crate::daemon_json(&app, "GET", "/api/peers/banned", None).await?;
+    let config: PeerConfig = store::load_json(&config_path(&app)?)?;
+    Ok(PeerOverview { connected: peers.peers, banned: banned.banned, preferred: config.preferred })
+}
+
+#[tauri::command]
+pub async fn add_preferred_peer(app: AppHandle, multiaddr: String, label: Option<String>) -> Result<(), String> {
+    let multiaddr = multiaddr.trim().to_string();
+    check_multiaddr(&multiaddr)?;
+    store::update_json(&config_path(&app)?, |c: &mut PeerConfig| {
+        if c.preferred.iter().any(|p| p.multiaddr == multiaddr) {
+            return Err("Already a preferred peer".to_string());
+        }
+        c.preferred.push(PreferredPeer {
+            multiaddr: multiaddr.clone(),
+            label: label.unwrap_or_default().trim().to_string(),
+            added_at: now_secs(),
+        });
+        Ok(())
+    })
+}
+
+// The running daemon keeps its connection; the peer is simply not passed
+// again on the next start
+#[tauri::command]
+pub async fn remove_preferred_peer(app: AppHandle, multiaddr: String) -> Result<(), String> {
+    store::update_json(&config_path(&app)?, |c: &mut PeerConfig| {
+        c.preferred.retain(|p| p.multiaddr != multiaddr);
+        Ok(())
+    })
+}
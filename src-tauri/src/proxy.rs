@@ -0,0 +1,301 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+// Network privacy. With the proxy on, the daemon is started with --proxy so
+// its peer traffic goes through SOCKS5 (Tor by default), and every request
+// the app itself makes off this machine is built by client() below. Calls
+// to the daemon on 127.0.0.1 stay direct and never use it. "No clearnet"
+// additionally stops the app from handing anything to the system browser
+// that would bypass the proxy: explorer links go to explorer_url instead,
+// or nowhere. A daemon that rejects --proxy is not started at all (see
+// start_daemon), so turning the proxy on never silently falls back.
+
+pub const PROXY_FLAG: &str = "--proxy";
+const TIMEOUT: Duration = Duration::from_secs(10);
+const TOR_CHECK_URL: &str = "https://check.torproject.org/api/ip";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub enabled: bool,
+    // host:port of a SOCKS5 proxy
+    pub address: String,
+    pub no_clearnet: bool,
+    // Explorer used while no_clearnet is on, e.g. an onion mirror; empty
+    // turns explorer links off
+    pub explorer_url: String,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1:9050".to_string(),
+            no_clearnet: false,
+            explorer_url: String::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProxyCheck {
+    // Answered a SOCKS5 greeting
+    pub reachable: bool,
+    // Whether the Tor Project says requests arrive from Tor; None when the
+    // check site could not be reached
+    pub is_tor: Option<bool>,
+    pub error: Option<String>,
+}
+
+fn check_address(address: &str) -> Result<(), String> {
+    let invalid = || Err(format!("Invalid proxy address: {}", address));
+    let Some((host, port)) = address.rsplit_once(':') else { return invalid() };
+    if port.parse::<u16>().map_or(true, |p| p == 0) {
+        return invalid();
+    }
+    let host = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')).unwrap_or(host);
+    let ok = host.parse::<IpAddr>().is_ok()
+        || (!host.is_empty() && host.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.'));
+    if ok { Ok(()) } else { invalid() }
+}
+
+fn check_explorer_url(raw: &str) -> Result<(), String> {
+    if raw.is_empty() {
+        return Ok(());
+    }
+    let url = url::Url::parse(raw).map_err(|e| format!("Invalid explorer URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err("Explorer URL must be an http or https address".to_string());
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err("Explorer URL must not have a query or fragment".to_string());
+    }
+    Ok(())
+}
+
+fn proxy_url(address: &str) -> String {
+    // socks5h: names are resolved by the proxy, so DNS does not leak
+    format!("socks5h://{}", address)
+}
+
+// An unreadable settings file is an error here rather than defaults, which
+// would quietly mean "no proxy"
+pub fn settings(app: &AppHandle) -> Result<ProxySettings, String> {
+    Ok(crate::settings::try_load(app)?.proxy)
+}
+
+// Extra start_daemon arguments
+pub fn startup_args(app: &AppHandle) -> Result<Vec<String>, String> {
+    let s = settings(app)?;
+    if !s.enabled || check_address(&s.address).is_err() {
+        return Ok(Vec::new());
+    }
+    Ok(vec![PROXY_FLAG.to_string(), s.address])
+}
+
+// HTTP client for anything that leaves this machine; callers pass
+// settings(app), or candidate settings when trying them out
+pub fn client(s: &ProxySettings) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder().timeout(TIMEOUT);
+    if s.enabled {
+        check_address(&s.address)?;
+        let proxy = reqwest::Proxy::all(proxy_url(&s.address))
+            .map_err(|e| format!("Failed to configure proxy: {}", e))?;
+        builder = builder.proxy(proxy);
+    } else if s.no_clearnet {
+        return Err("Clearnet requests are blocked and no proxy is set".to_string());
+    } else {
+        // Environment proxies are for the user's browser, not this app
+        builder = builder.no_proxy();
+    }
+    builder.build().map_err(|e| format!("HTTP client error: {}", e))
+}
+
+// Where an explorer link may go, given the public explorer's base URL
+pub fn explorer_base(app: &AppHandle, public: &str) -> Result<String, String> {
+    let s = settings(app)?;
+    if !s.no_clearnet {
+        return Ok(public.to_string());
+    }
+    if s.explorer_url.is_empty() {
+        return Err("Explorer links are off while clearnet is blocked".to_string());
+    }
+    Ok(s.explorer_url.trim_end_matches('/').to_string())
+}
+
+// Asks for the no-authentication method and expects the proxy to accept it
+async fn socks_greeting(address: &str) -> Result<(), String> {
+    let mut stream = tokio::net::TcpStream::connect(address)
+        .await
+        .map_err(|e| format!("Failed to connect to proxy: {}", e))?;
+    stream.write_all(&[0x05, 0x01, 0x00]).await.map_err(|e| format!("Failed to talk to proxy: {}", e))?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await.map_err(|e| format!("Failed to talk to proxy: {}", e))?;
+    match reply {
+        [0x05, 0x00] => Ok(()),
+        [0x05, _] => Err("Proxy requires authentication".to_string()),
+        _ => Err("Not a SOCKS5 proxy".to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct TorCheck {
+    #[serde(rename = "IsTor")]
+    is_tor: bool,
+}
+
+async fn tor_check(s: &ProxySettings) -> Result<bool, String> {
+    let check: TorCheck = client(s)?
+        .get(TOR_CHECK_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Request through proxy failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected response from Tor check: {}", e))?;
+    Ok(check.is_tor)
+}
+
+#[tauri::command]
+pub fn get_proxy_settings(app: AppHandle) -> Result<ProxySettings, String> {
+    settings(&app)
+}
+
+// Takes effect for the daemon on its next start
+#[tauri::command]
+pub fn set_proxy_settings(app: AppHandle, settings: ProxySettings) -> Result<(), String> {
+    let settings = ProxySettings {
+        address: settings.address.trim().to_string(),
+        explorer_url: settings.explorer_url.trim().to_string(),
+        ..settings
+    };
+    check_address(&settings.address)?;
+    check_explorer_url(&settings.explorer_url)?;
+    crate::settings::update(&app, |s| s.proxy = settings)?;
+    Ok(())
+}
+
+// Tries a proxy address before it is saved: first that something there
+// speaks SOCKS5, which is all a local stand-in needs to pass, then a real
+// request through it to see whether it leads into Tor
+#[tauri::command]
+pub async fn test_proxy(address: String) -> Result<ProxyCheck, String> {
+    let address = address.trim().to_string();
+    check_address(&address)?;
+    if let Err(e) = tokio::time::timeout(TIMEOUT, socks_greeting(&address))
+        .await
+        .unwrap_or_else(|_| Err("Proxy did not answer".to_string()))
+    {
+        return Ok(ProxyCheck { reachable: false, is_tor: None, error: Some(e) });
+    }
+    let s = ProxySettings { enabled: true, address, ..ProxySettings::default() };
+    Ok(match tor_check(&s).await {
+        Ok(is_tor) => ProxyCheck { reachable: true, is_tor: Some(is_tor), error: None },
+        Err(e) => ProxyCheck { reachable: true, is_tor: None, error: Some(e) },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    // Loopback SOCKS5 stand-in: answers the greeting with `reply`, and when
+    // that accepts, takes one CONNECT and serves a fixed HTTP response on
+    // the same stream. Resolves to the host name the client asked for.
+    async fn stand_in(reply: [u8; 2]) -> (String, tokio::task::JoinHandle<Option<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let task = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.ok()?;
+            let mut greeting = [0u8; 2];
+            stream.read_exact(&mut greeting).await.ok()?;
+            let mut methods = vec![0u8; greeting[1] as usize];
+            stream.read_exact(&mut methods).await.ok()?;
+            stream.write_all(&reply).await.ok()?;
+            if reply != [0x05, 0x00] {
+                return None;
+            }
+
+            // VER CMD RSV ATYP, then a domain name since socks5h leaves
+            // resolving to the proxy
+            let mut head = [0u8; 4];
+            stream.read_exact(&mut head).await.ok()?;
+            assert_eq!(head, [0x05, 0x01, 0x00, 0x03]);
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len).await.ok()?;
+            let mut host = vec![0u8; len[0] as usize + 2];
+            stream.read_exact(&mut host).await.ok()?;
+            host.truncate(len[0] as usize);
+            stream.write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).await.ok()?;
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.ok()?;
+                if n == 0 {
+                    return None;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok").await.ok()?;
+            String::from_utf8(host).ok()
+        });
+        (address, task)
+    }
+
+    fn proxied(address: &str) -> ProxySettings {
+        ProxySettings { enabled: true, address: address.to_string(), ..ProxySettings::default() }
+    }
+
+    #[tokio::test]
+    async fn greeting_accepts_a_socks5_proxy() {
+        let (address, _task) = stand_in([0x05, 0x00]).await;
+        assert_eq!(socks_greeting(&address).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn greeting_reports_authentication() {
+        let (address, _task) = stand_in([0x05, 0xff]).await;
+        assert_eq!(socks_greeting(&address).await, Err("Proxy requires authentication".to_string()));
+    }
+
+    #[tokio::test]
+    async fn greeting_rejects_other_servers() {
+        let (address, _task) = stand_in(*b"HT").await;
+        assert_eq!(socks_greeting(&address).await, Err("Not a SOCKS5 proxy".to_string()));
+    }
+
+    #[tokio::test]
+    async fn client_sends_requests_and_names_through_the_proxy() {
+        let (address, task) = stand_in([0x05, 0x00]).await;
+        let body = client(&proxied(&address))
+            .unwrap()
+            .get("http://explorer.invalid/status")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+        assert_eq!(task.await.unwrap().as_deref(), Some("explorer.invalid"));
+    }
+
+    #[test]
+    fn client_refuses_clearnet_without_a_proxy() {
+        let s = ProxySettings { enabled: false, no_clearnet: true, ..ProxySettings::default() };
+        assert!(client(&s).is_err());
+        let s = ProxySettings { no_clearnet: false, ..s };
+        assert!(client(&s).is_ok());
+    }
+
+    #[test]
+    fn client_rejects_a_bad_proxy_address() {
+        assert!(client(&proxied("not an address")).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+// Daemon startup is three waits, each with its own timeout so a failure
+// names the step that stalled: the daemon writes api.cookie, binds the API
+// port, then answers /api/status. Progress goes out as daemon-stage events.
+
+const API_ADDR: &str = "127.0.0.1:8332";
+const COOKIE_TIMEOUT: Duration = Duration::from_secs(30);
+const LISTENER_TIMEOUT: Duration = Duration::from_secs(15);
+const STATUS_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Serialize)]
+pub struct StageEvent {
+    pub stage: &'static str,
+    // waiting | done | failed
+    pub status: &'static str,
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+fn emit(app: &AppHandle, stage: &'static str, status: &'static str, started: Instant, error: Option<String>) {
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let _ = app.emit("daemon-stage", StageEvent { stage, status, elapsed_ms, error });
+}
+
+pub async fn listener_up() -> bool {
+    let connect = tokio::net::TcpStream::connect(API_ADDR);
+    matches!(tokio::time::timeout(Duration::from_millis(300), connect).await, Ok(Ok(_)))
+}
+
+// Some(reason) once the daemon we spawned has exited; None while it runs or
+// when it was not started by us
+fn child_exited(app: &AppHandle) -> Option<String> {
+    let state = app.state::<crate::DaemonState>();
+    let mut guard = state.child.lock().ok()?;
+    let status = guard.as_mut()?.try_wait().ok()??;
+    *guard = None;
+    Some(format!("Daemon exited with code: {}", status))
+}
+
+fn child_running(app: &AppHandle) -> bool {
+    let state = app.state::<crate::DaemonState>();
+    let Ok(mut guard) = state.child.lock() else { return false };
+    matches!(guard.as_mut().map(|c| c.try_wait()), Some(Ok(None)))
+}
+
+// The cookie belongs to whichever daemon wrote it. It is only stale when no
+// process of ours is alive and nothing holds the API port.
+pub async fn daemon_gone(app: &AppHandle) -> bool {
+    !child_running(app) && !listener_up().await
+}
+
+async fn wait_cookie(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let ready = || std::fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false);
+    if ready() {
+        return Ok(());
+    }
+
+    // The watcher wakes us as soon as the file appears; polling remains as a
+    // fallback for filesystems that do not deliver events
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _watcher = path.parent().and_then(|dir| {
+        use notify::Watcher;
+        let mut watcher = notify::recommended_watcher(move |_| {
+            let _ = tx.send(());
+        })
+        .ok()?;
+        watcher.watch(dir, notify::RecursiveMode::NonRecursive).ok()?;
+        Some(watcher)
+    });
+
+    let deadline = Instant::now() + COOKIE_TIMEOUT;
+    while Instant::now() < deadline {
+        if ready() {
+            return Ok(());
+        }
+        if let Some(reason) = child_exited(app) {
+            return Err(reason);
+        }
+        tokio::select! {
+            _ = rx.recv() => {}
+            _ = tokio::time::sleep(POLL) => {}
+        }
+    }
+    Err("Timed out waiting for the daemon to write its API cookie".to_string())
+}
+
+async fn wait_listener(app: &AppHandle) -> Result<(), String> {
+    let deadline = Instant::now() + LISTENER_TIMEOUT;
+    while Instant::now() < deadline {
+        let connect = tokio::net::TcpStream::connect(API_ADDR);
+        if let Ok(Ok(_)) = tokio::time::timeout(Duration::from_secs(1), connect).await {
+            return Ok(());
+        }
+        if let Some(reason) = child_exited(app) {
+            return Err(reason);
+        }
+        tokio::time::sleep(POLL).await;
+    }
+    Err("Timed out waiting for the daemon API to accept connections".to_string())
+}
+
+async fn status_ok(app: &AppHandle) -> bool {
+    let Ok(token) = crate::read_api_token(app) else { return false };
+    let Ok(client) = reqwest::Client::builder().timeout(Duration::from_secs(2)).build() else {
+        return false;
+    };
+    client
+        .get(format!("http://{}/api/status", API_ADDR))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .map(|r| r.status().is_success())
+        .unwrap_or(false)
+}
+
+async fn wait_status(app: &AppHandle) -> Result<(), String> {
+    let deadline = Instant::now() + STATUS_TIMEOUT;
+    while Instant::now() < deadline {
+        if status_ok(app).await {
+            return Ok(());
+        }
+        if let Some(reason) = child_exited(app) {
+            return Err(reason);
+        }
+        tokio::time::sleep(POLL).await;
+    }
+    Err("Timed out waiting for the daemon to report status".to_string())
+}
+
+pub async fn wait_until_ready(app: &AppHandle) -> Result<(), String> {
+    let cookie = crate::get_data_dir(app)?.join("api.cookie");
+
+    let started = Instant::now();
+    emit(app, "cookie", "waiting", started, None);
+    if let Err(e) = wait_cookie(app, &cookie).await {
+        emit(app, "cookie", "failed", started, Some(e.clone()));
+        return Err(e);
+    }
+    emit(app, "cookie", "done", started, None);
+
+    let started = Instant::now();
+    emit(app, "listener", "waiting", started, None);
+    if let Err(e) = wait_listener(app).await {
+        emit(app, "listener", "failed", started, Some(e.clone()));
+        return Err(e);
+    }
+    emit(app, "listener", "done", started, None);
+
+    let started = Instant::now();
+    emit(app, "status", "waiting", started, None);
+    if let Err(e) = wait_status(app).await {
+        emit(app, "status", "failed", started, Some(e.clone()));
+        return Err(e);
+    }
+    emit(app, "status", "done", started, None);
+    crate::mining_resume::on_ready(app).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn wait_for_daemon(app: AppHandle) -> Result<(), String> {
+    wait_until_ready(&app).await
+}
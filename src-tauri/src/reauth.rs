@@ -0,0 +1,147 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+// An unlocked wallet is not the same as the person at the keyboard still
+// being its owner. Sensitive commands call require() and fail with
+// REAUTH_REQUIRED until confirm_password has checked a freshly typed
+// password against the daemon.
+
+pub const REAUTH_REQUIRED: &str = "Password confirmation required";
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strictness {
+    // Every sensitive operation needs its own confirmation
+    Always,
+    // One confirmation covers window_secs
+    Window,
+    // As Window, but sends go through without asking
+    SendsExempt,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Send,
+    RevealSeed,
+    DeleteWallet,
+    ResetChain,
+    // Relaxing this or the auto-lock settings
+    LowerProtection,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReauthSettings {
+    pub strictness: Strictness,
+    pub window_secs: u64,
+}
+
+impl Default for ReauthSettings {
+    fn default() -> Self {
+        Self { strictness: Strictness::Window, window_secs: 120 }
+    }
+}
+
+impl ReauthSettings {
+    fn asks_less_than(&self, current: &ReauthSettings) -> bool {
+        let rank = |s: Strictness| match s {
+            Strictness::Always => 0,
+            Strictness::Window => 1,
+            Strictness::SendsExempt => 2,
+        };
+        rank(self.strictness) > rank(current.strictness)
+            || (self.strictness != Strictness::Always && self.window_secs > current.window_secs)
+    }
+}
+
+pub struct ReauthState {
+    confirmed_at: Mutex<Option<Instant>>,
+    settings: Mutex<ReauthSettings>,
+}
+
+impl ReauthState {
+    pub fn new(settings: ReauthSettings) -> Self {
+        Self { confirmed_at: Mutex::new(None), settings: Mutex::new(settings) }
+    }
+
+    fn settings(&self) -> ReauthSettings {
+        self.settings.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+// Any lock ends the confirmation window, whoever unlocks next starts over
+pub fn clear(app: &AppHandle) {
+    if let Ok(mut at) = app.state::<ReauthState>().confirmed_at.lock() {
+        *at = None;
+    }
+}
+
+pub fn require(app: &AppHandle, op: Operation) -> Result<(), String> {
+    let state = app.state::<ReauthState>();
+    let settings = state.settings();
+    if op == Operation::Send && settings.strictness == Strictness::SendsExempt {
+        return Ok(());
+    }
+    let mut at = state.confirmed_at.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let fresh = at.is_some_and(|t| t.elapsed() < Duration::from_secs(settings.window_secs));
+    if !fresh {
+        *at = None;
+        return Err(REAUTH_REQUIRED.to_string());
+    }
+    if settings.strictness == Strictness::Always {
+        *at = None;
+    }
+    Ok(())
+}
+
+// The daemon has no check-only endpoint, so this unlocks the loaded wallet:
+// a no-op when it is already unlocked with that password, 401 otherwise.
+// A wallet that was locked is locked again straight after, so confirming
+// never opens it behind the lock screen. Deleting another wallet file is
+// therefore authorised by the wallet currently open.
+#[tauri::command]
+pub async fn confirm_password(app: AppHandle, password: String) -> Result<(), String> {
+    let was_unlocked = crate::autolock::is_unlocked(&app);
+    let body = serde_json::json!({ "password": password });
+    let checked = crate::daemon_json::<serde_json::Value>(&app, "POST", "/api/wallet/unlock", Some(body))
+        .await
+        .map_err(|e| {
+            if e.contains("incorrect password") { "Incorrect password".to_string() } else { e }
+        });
+    if !was_unlocked {
+        crate::daemon_request(&app, "POST", "/api/wallet/lock", None)
+            .await
+            .map_err(|e| format!("Failed to re-lock wallet: {}", e))?;
+    }
+    checked?;
+    let state = app.state::<ReauthState>();
+    let mut at = state.confirmed_at.lock().map_err(|e| format!("Lock error: {}", e))?;
+    *at = Some(Instant::now());
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_reauth_settings(state: State<'_, ReauthState>) -> ReauthSettings {
+    state.settings()
+}
+
+#[tauri::command]
+pub fn set_reauth_settings(
+    app: AppHandle,
+    state: State<'_, ReauthState>,
+    settings: ReauthSettings,
+) -> Result<(), String> {
+    if settings.strictness != Strictness::Always && settings.window_secs == 0 {
+        return Err("Confirmation window must be at least one second".to_string());
+    }
+    if settings.asks_less_than(&state.settings()) {
+        require(&app, Operation::LowerProtection)?;
+    }
+    crate::settings::update(&app, |s| s.reauth = settings.clone())?;
+    let mut guard = state.settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    *guard = settings;
+    Ok(())
+}
@@ -0,0 +1,291 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::annotations::output_key;
+use crate::models::{BlockHeader, DaemonStats, HistoryResponse, OutputEntry, TxLookup};
+use crate::store;
+
+// The daemon reports which block height each of our outputs is in, but not
+// whether that block is still the one it was. For every height holding an
+// output that is not yet final, wallet-data/<wallet>/output-blocks.json
+// keeps the block hash seen when the output first showed up, and each
+// new_block compares it with what /api/block/{height} returns now. A
+// mismatch flags the outputs that were in the replaced block and raises a
+// reorg-detected event; a flag clears once /api/tx finds the transaction in
+// the current chain again. Heights past the final threshold are dropped,
+// so the file and the work per block stay small.
+
+// Upper bound on the thresholds, which also bounds how many blocks are watched
+const MAX_FINAL: u64 = 1000;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfirmationSettings {
+    pub final_confirmations: u64,
+    // Coinbase outputs can only be spent after maturing, so they are
+    // usually held to a higher bar
+    pub coinbase_final_confirmations: u64,
+}
+
+impl Default for ConfirmationSettings {
+    fn default() -> Self {
+        Self { final_confirmations: 10, coinbase_final_confirmations: 60 }
+    }
+}
+
+impl ConfirmationSettings {
+    fn threshold(&self, coinbase: bool) -> u64 {
+        if coinbase { self.coinbase_final_confirmations } else { self.final_confirmations }
+    }
+
+    fn watch_depth(&self) -> u64 {
+        self.final_confirmations.max(self.coinbase_final_confirmations)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct TrackedBlock {
+    hash: String,
+    // Output keys seen at this height
+    outputs: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Reorged {
+    pub height: u64,
+    pub old_hash: String,
+    // None when the chain no longer reaches that height
+    pub new_hash: Option<String>,
+    pub detected_at: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Tracked {
+    blocks: BTreeMap<u64, TrackedBlock>,
+    // Output key -> the reorg that took its block away
+    reorged: BTreeMap<String, Reorged>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ReorgEvent {
+    pub wallet: String,
+    pub height: u64,
+    pub old_hash: String,
+    pub new_hash: Option<String>,
+    pub outputs: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct OutputConfirmations {
+    pub confirmations: u64,
+    pub final_after: u64,
+    pub is_final: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reorged: Option<Reorged>,
+}
+
+#[derive(Default)]
+pub struct ReorgState {
+    // Keeps two new_block events from checking at the same time
+    busy: Mutex<bool>,
+}
+
+fn tracked_path(app: &AppHandle, wallet: &str) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_wallet_data_dir_for(app, wallet)?.join("output-blocks.json"))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn confirmations(height: u64, tip: u64) -> u64 {
+    if height > tip { 0 } else { tip - height + 1 }
+}
+
+// None when the daemon has no block at that height
+async fn block_hash(app: &AppHandle, height: u64) -> Result<Option<String>, String> {
+    let path = format!("/api/block/{}", height);
+    let (status, text) = crate::daemon_request_status(app, "GET", &path, None).await?;
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(text);
+    }
+    let block: BlockHeader = serde_json::from_str(&text).map_err(|e| format!("Unexpected block from daemon: {}", e))?;
+    Ok(Some(block.hash))
+}
+
+// Whether the transaction is in a block of the current chain
+async fn tx_confirmed(app: &AppHandle, txid: &str) -> Result<bool, String> {
+    let path = format!("/api/tx/{}", txid);
+    let (status, text) = crate::daemon_request_status(app, "GET", &path, None).await?;
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+    if !status.is_success() {
+        return Err(text);
+    }
+    let found: TxLookup = serde_json::from_str(&text).map_err(|e| format!("Unexpected transaction from daemon: {}", e))?;
+    Ok(!found.in_mempool && found.block_height.is_some())
+}
+
+async fn check(app: &AppHandle) -> Result<(), String> {
+    let wallet = crate::get_active_wallet_name(app)?;
+    let path = tracked_path(app, &wallet)?;
+    let stats: DaemonStats = crate::daemon_json(app, "GET", "/api/status", None).await?;
+    // A syncing daemon is missing blocks, not replacing them
+    if stats.syncing {
+        return Ok(());
+    }
+    let tip = stats.chain_height;
+    let history: HistoryResponse = crate::daemon_json(app, "GET", "/api/wallet/history", None).await?;
+    let settings = crate::settings::try_load(app)?.confirmations;
+    let mut tracked: Tracked = store::load_json(&path)?;
+    let mut events = Vec::new();
+
+    // Compare what we saw with the chain as it is now
+    let heights: Vec<u64> = tracked.blocks.keys().copied().collect();
+    for height in heights {
+        if height > tip {
+            continue;
+        }
+        let current = block_hash(app, height).await?;
+        let Some(seen) = tracked.blocks.get(&height) else { continue };
+        if current.as_deref() == Some(seen.hash.as_str()) {
+            continue;
+        }
+        let Some(seen) = tracked.blocks.remove(&height) else { continue };
+        for key in &seen.outputs {
+            tracked.reorged.insert(key.clone(), Reorged {
+                height,
+                old_hash: seen.hash.clone(),
+                new_hash: current.clone(),
+                detected_at: now_secs(),
+            });
+        }
+        events.push(ReorgEvent {
+            wallet: wallet.clone(),
+            height,
+            old_hash: seen.hash,
+            new_hash: current,
+            outputs: seen.outputs,
+        });
+    }
+
+    // Flagged outputs the daemon no longer lists are gone for good; the
+    // others are cleared once their transaction is back in a block
+    let listed: BTreeMap<String, &OutputEntry> =
+        history.outputs.iter().map(|o| (output_key(&o.txid, o.output_index), o)).collect();
+    tracked.reorged.retain(|key, _| listed.contains_key(key));
+    let flagged: Vec<String> = tracked.reorged.keys().cloned().collect();
+    for key in flagged {
+        let Some(output) = listed.get(&key) else { continue };
+        if tx_confirmed(app, &output.txid).await? {
+            tracked.reorged.remove(&key);
+        }
+    }
+
+    // Start watching heights of outputs that are not final yet
+    for output in &history.outputs {
+        let key = output_key(&output.txid, output.output_index);
+        let depth = confirmations(output.block_height, tip);
+        if depth == 0 || depth > settings.threshold(output.is_coinbase) || tracked.reorged.contains_key(&key) {
+            continue;
+        }
+        let block = match tracked.blocks.entry(output.block_height) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                let Some(hash) = block_hash(app, output.block_height).await? else { continue };
+                e.insert(TrackedBlock { hash, outputs: Vec::new() })
+            }
+        };
+        if !block.outputs.contains(&key) {
+            block.outputs.push(key);
+        }
+    }
+    tracked.blocks.retain(|height, _| confirmations(*height, tip) <= settings.watch_depth());
+
+    store::update_json(&path, |t: &mut Tracked| {
+        *t = std::mem::take(&mut tracked);
+        Ok(())
+    })?;
+    for event in events {
+        let _ = app.emit("reorg-detected", event);
+    }
+    Ok(())
+}
+
+// Sets the busy flag, returning what it was before
+fn set_busy(app: &AppHandle, busy: bool) -> bool {
+    let state = app.state::<ReorgState>();
+    let mut flag = match state.busy.lock() {
+        Ok(flag) => flag,
+        Err(_) => return true,
+    };
+    std::mem::replace(&mut *flag, busy)
+}
+
+// Called from events for every new_block
+pub fn on_new_block(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if set_busy(&app, true) {
+            return;
+        }
+        if let Err(e) = check(&app).await {
+            eprintln!("Reorg check failed: {}", e);
+        }
+        set_busy(&app, false);
+    });
+}
+
+// Confirmation counts for a set of outputs against the given tip, keyed
+// like annotations::output_key
+pub fn confirmations_for(app: &AppHandle, outputs: &[OutputEntry], tip: u64) -> BTreeMap<String, OutputConfirmations> {
+    let settings = crate::settings::load(app).confirmations;
+    let tracked: Tracked = crate::get_active_wallet_name(app)
+        .and_then(|w| tracked_path(app, &w))
+        .and_then(|p| store::load_json(&p))
+        .unwrap_or_default();
+    outputs
+        .iter()
+        .map(|o| {
+            let key = output_key(&o.txid, o.output_index);
+            let reorged = tracked.reorged.get(&key).cloned();
+            let confirmations = if reorged.is_some() { 0 } else { confirmations(o.block_height, tip) };
+            let final_after = settings.threshold(o.is_coinbase);
+            let status = OutputConfirmations {
+                confirmations,
+                final_after,
+                is_final: confirmations >= final_after,
+                reorged,
+            };
+            (key, status)
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_confirmation_settings(app: AppHandle) -> Result<ConfirmationSettings, String> {
+    Ok(crate::settings::try_load(&app)?.confirmations)
+}
+
+#[tauri::command]
+pub fn set_confirmation_settings(app: AppHandle, settings: ConfirmationSettings) -> Result<(), String> {
+    let valid = |n: u64| (1..=MAX_FINAL).contains(&n);
+    if !valid(settings.final_confirmations) || !valid(settings.coinbase_final_confirmations) {
+        return Err(format!("Confirmation thresholds must be between 1 and {}", MAX_FINAL));
+    }
+    crate::settings::update(&app, |s| s.confirmations = settings)?;
+    Ok(())
+}
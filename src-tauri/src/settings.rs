@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::autolock::AutoLockSettings;
+use crate::health::HealthSettings;
+use crate::mining_schedule::MiningSchedule;
+use crate::proxy::ProxySettings;
+use crate::reauth::ReauthSettings;
+use crate::reorg::ConfirmationSettings;
+use crate::store;
+use crate::throttle::ThrottleSettings;
+
+// Backend-owned preferences, persisted as settings.json in the app dir.
+// Every section is #[serde(default)] so older files keep loading as new
+// sections are added.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub autolock: AutoLockSettings,
+    pub reauth: ReauthSettings,
+    pub mining_schedule: MiningSchedule,
+    pub throttle: ThrottleSettings,
+    pub health: HealthSettings,
+    pub proxy: ProxySettings,
+    pub confirmations: ConfirmationSettings,
+}
+
+fn settings_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_app_dir(app)?.join("settings.json"))
+}
+
+// A file that fails to parse is an error rather than defaults, so callers
+// can tell and update() cannot overwrite the user's settings with them.
+pub fn try_load(app: &AppHandle) -> Result<Settings, String> {
+    store::load_json(&settings_path(app)?)
+}
+
+// For readers with nothing better to do than run on defaults; the error is
+// logged, and update() still leaves the unreadable file alone
+pub fn load(app: &AppHandle) -> Settings {
+    try_load(app).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        Settings::default()
+    })
+}
+
+// Read-modify-write helper so callers only touch their own section
+pub fn update<F: FnOnce(&mut Settings)>(app: &AppHandle, f: F) -> Result<Settings, String> {
+    store::update_json(&settings_path(app)?, |settings: &mut Settings| {
+        f(settings);
+        Ok(settings.clone())
+    })
+}
@@ -0,0 +1,263 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// One app instance per data dir. The first launch holds an OS lock on
+// instance.lock for its whole lifetime and listens on a local socket; later
+// launches find the lock taken, forward their argv over the socket and exit
+// before they can spawn a second daemon or kill the first one's port.
+// On Windows the socket is loopback TCP, which any local process can reach,
+// so each handoff must open with a random key that only readers of our app
+// dir can know.
+
+const LOCK_FILE: &str = "instance.lock";
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+const MAX_PAYLOAD: u64 = 64 * 1024;
+#[cfg(unix)]
+const SOCKET_FILE: &str = "instance.sock";
+#[cfg(windows)]
+const PORT_FILE: &str = "instance.port";
+#[cfg(windows)]
+const KEY_FILE: &str = "instance.key";
+
+#[cfg(unix)]
+type Listener = std::os::unix::net::UnixListener;
+#[cfg(windows)]
+type Listener = std::net::TcpListener;
+
+pub struct InstanceGuard {
+    _lock: std::fs::File,
+    app_dir: PathBuf,
+    listener: Listener,
+    #[cfg(windows)]
+    key: String,
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        let _ = std::fs::remove_file(self.app_dir.join(SOCKET_FILE));
+        #[cfg(windows)]
+        {
+            let _ = std::fs::remove_file(self.app_dir.join(PORT_FILE));
+            let _ = std::fs::remove_file(self.app_dir.join(KEY_FILE));
+        }
+    }
+}
+
+// Args that arrive before the webview has registered its listeners (our own
+// argv, or a macOS open-URL event during launch) are held here until the
+// frontend calls frontend_ready.
+pub struct LaunchArgs {
+    pending: Mutex<Option<Vec<String>>>,
+}
+
+impl LaunchArgs {
+    pub fn new(args: Vec<String>) -> Self {
+        Self { pending: Mutex::new(Some(args)) }
+    }
+}
+
+// Some(guard) when we are the primary instance, None when another one
+// already holds the lock.
+pub fn acquire(app_dir: &Path) -> Result<Option<InstanceGuard>, String> {
+    std::fs::create_dir_all(app_dir)
+        .map_err(|e| format!("Failed to create app dir: {}", e))?;
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(app_dir.join(LOCK_FILE))
+        .map_err(|e| format!("Failed to open instance lock: {}", e))?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(std::fs::TryLockError::WouldBlock) => return Ok(None),
+        Err(std::fs::TryLockError::Error(e)) => return Err(format!("Failed to lock instance file: {}", e)),
+    }
+    #[cfg(unix)]
+    let listener = bind(app_dir)?;
+    #[cfg(windows)]
+    let (listener, key) = bind(app_dir)?;
+    Ok(Some(InstanceGuard {
+        _lock: file,
+        app_dir: app_dir.to_path_buf(),
+        listener,
+        #[cfg(windows)]
+        key,
+    }))
+}
+
+#[cfg(unix)]
+fn bind(app_dir: &Path) -> Result<Listener, String> {
+    let path = app_dir.join(SOCKET_FILE);
+    // Holding the lock means any socket file left here is stale
+    let _ = std::fs::remove_file(&path);
+    Listener::bind(&path)
+        .map_err(|e| format!("Failed to bind instance socket: {}", e))
+}
+
+// The key is written before the port, so a launch that can see the port can
+// always read the key that goes with it
+#[cfg(windows)]
+fn bind(app_dir: &Path) -> Result<(Listener, String), String> {
+    use rand::Rng;
+
+    let listener = Listener::bind(("127.0.0.1", 0))
+        .map_err(|e| format!("Failed to bind instance socket: {}", e))?;
+    let port = listener.local_addr()
+        .map_err(|e| format!("Failed to read instance socket address: {}", e))?
+        .port();
+    let bytes: [u8; 16] = rand::thread_rng().gen();
+    let key: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    std::fs::write(app_dir.join(KEY_FILE), &key)
+        .map_err(|e| format!("Failed to publish instance key: {}", e))?;
+    std::fs::write(app_dir.join(PORT_FILE), port.to_string())
+        .map_err(|e| format!("Failed to publish instance port: {}", e))?;
+    Ok((listener, key))
+}
+
+// Sends our argv to the primary instance. The primary may still be starting
+// up and not listening yet, so retry for a couple of seconds.
+pub fn forward(app_dir: &Path, args: &[String]) -> Result<(), String> {
+    let mut last_err = String::new();
+    for _ in 0..20 {
+        match connect(app_dir) {
+            Ok((mut stream, payload)) => {
+                let payload = payload_for(args, payload)?;
+                return stream
+                    .write_all(format!("{}\n", payload).as_bytes())
+                    .map_err(|e| format!("Failed to forward args: {}", e));
+            }
+            Err(e) => last_err = e,
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    Err(last_err)
+}
+
+// The payload is a JSON array of our args, led by the instance key where
+// the transport needs one
+fn payload_for(args: &[String], key: Option<String>) -> Result<String, String> {
+    let fields: Vec<&str> = key.as_deref().into_iter().chain(args.iter().map(String::as_str)).collect();
+    serde_json::to_string(&fields).map_err(|e| format!("Failed to encode args: {}", e))
+}
+
+#[cfg(unix)]
+fn connect(app_dir: &Path) -> Result<(std::os::unix::net::UnixStream, Option<String>), String> {
+    std::os::unix::net::UnixStream::connect(app_dir.join(SOCKET_FILE))
+        .map(|stream| (stream, None))
+        .map_err(|e| format!("Failed to reach running instance: {}", e))
+}
+
+#[cfg(windows)]
+fn connect(app_dir: &Path) -> Result<(std::net::TcpStream, Option<String>), String> {
+    let port: u16 = std::fs::read_to_string(app_dir.join(PORT_FILE))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .ok_or("Running instance has not published its port yet".to_string())?;
+    let key = std::fs::read_to_string(app_dir.join(KEY_FILE))
+        .map_err(|e| format!("Failed to read instance key: {}", e))?;
+    std::net::TcpStream::connect(("127.0.0.1", port))
+        .map(|stream| (stream, Some(key.trim().to_string())))
+        .map_err(|e| format!("Failed to reach running instance: {}", e))
+}
+
+// Strips and checks the leading key on transports that carry one; a
+// payload without the right key is not one of our launches
+#[cfg(unix)]
+fn authenticate(_guard: &InstanceGuard, args: Vec<String>) -> Option<Vec<String>> {
+    Some(args)
+}
+
+#[cfg(windows)]
+fn authenticate(guard: &InstanceGuard, mut args: Vec<String>) -> Option<Vec<String>> {
+    if args.is_empty() || args[0] != guard.key {
+        return None;
+    }
+    args.remove(0);
+    Some(args)
+}
+
+// Accepts handoffs from later launches for as long as the app runs. A
+// client that connects and then says nothing is dropped after READ_TIMEOUT
+// so it cannot hold up the launches behind it.
+pub fn listen(guard: InstanceGuard, app: AppHandle) {
+    std::thread::spawn(move || {
+        for stream in guard.listener.incoming().flatten() {
+            if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+                continue;
+            }
+            let mut line = String::new();
+            if BufReader::new(stream).take(MAX_PAYLOAD).read_line(&mut line).is_err() {
+                continue;
+            }
+            let Ok(args) = serde_json::from_str::<Vec<String>>(line.trim()) else {
+                continue;
+            };
+            if let Some(args) = authenticate(&guard, args) {
+                handoff(&app, args);
+            }
+        }
+    });
+}
+
+fn handoff(app: &AppHandle, args: Vec<String>) {
+    if let Some(w) = app.get_webview_window("main") {
+        let _ = w.unminimize();
+        let _ = w.show();
+        let _ = w.set_focus();
+    }
+    deliver(app, args);
+}
+
+// Queues args until the frontend is listening, then dispatches them
+pub fn deliver(app: &AppHandle, args: Vec<String>) {
+    let state = app.state::<LaunchArgs>();
+    if let Ok(mut guard) = state.pending.lock() {
+        if let Some(pending) = guard.as_mut() {
+            pending.extend(args);
+            return;
+        }
+    }
+    dispatch(app, args);
+}
+
+// A wallet file the OS asked us to open
+fn is_wallet_file(arg: &str) -> bool {
+    let path = Path::new(arg);
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case("dat")) && path.is_file()
+}
+
+fn dispatch(app: &AppHandle, args: Vec<String>) {
+    let (uris, rest): (Vec<String>, Vec<String>) = args
+        .into_iter()
+        .partition(|a| crate::payment_uri::is_payment_uri(a));
+    for uri in uris {
+        crate::payment_uri::dispatch(app, &uri);
+    }
+    let (files, rest): (Vec<String>, Vec<String>) = rest.into_iter().partition(|a| is_wallet_file(a));
+    for file in files {
+        match crate::copy_wallet_file(app, Path::new(&file)) {
+            Ok(name) => {
+                let _ = app.emit("wallet-file-opened", name);
+            }
+            Err(e) => {
+                let _ = app.emit("wallet-file-error", e);
+            }
+        }
+    }
+    if !rest.is_empty() {
+        let _ = app.emit("instance-args", rest);
+    }
+}
+
+#[tauri::command]
+pub fn frontend_ready(app: AppHandle, state: State<'_, LaunchArgs>) -> Result<(), String> {
+    let pending = state.pending.lock().map_err(|e| format!("Lock error: {}", e))?.take();
+    if let Some(args) = pending {
+        dispatch(&app, args);
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// JSON files for the per-wallet stores. A missing file is an empty store; a
+// file that fails to parse is an error, so a bad read is never followed by
+// a save that wipes the user's data.
+
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+// Hidden sibling of `path`, so the final rename never crosses filesystems.
+// The counter keeps two writers in this process off each other's temp file.
+pub fn temp_path(path: &Path) -> PathBuf {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, std::process::id(), n))
+}
+
+// Write-then-rename so a crash mid-save leaves the previous version intact
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let tmp = temp_path(path);
+    if let Err(e) = std::fs::write(&tmp, contents) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(format!("Failed to write {}: {}", path.display(), e));
+    }
+    std::fs::rename(&tmp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
+
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    write_atomic(path, json.as_bytes())
+}
+
+// One lock for all stores: writes are rare and tiny, and this keeps two
+// commands from interleaving a read-modify-write on the same file.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn update_json<T, R, F>(path: &Path, f: F) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+    F: FnOnce(&mut T) -> Result<R, String>,
+{
+    let _guard = WRITE_LOCK.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut value = load_json(path)?;
+    let out = f(&mut value)?;
+    save_json(path, &value)?;
+    Ok(out)
+}
@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::models::{BlockHeader, DaemonStats};
+
+// Peers do not report their heights, so the network tip is estimated from
+// time: the tip block's age divided by the chain's average block interval
+// is roughly how many blocks we are missing. The download rate comes from
+// height samples over the last minute.
+
+const TICK: Duration = Duration::from_secs(5);
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+// A tip younger than this many average intervals counts as caught up
+const CAUGHT_UP_INTERVALS: f64 = 2.0;
+// The target block time, for when the chain is too short to average
+pub const FALLBACK_BLOCK_SECS: f64 = 300.0;
+
+#[derive(Clone, Default, Serialize)]
+pub struct SyncProgress {
+    pub syncing: bool,
+    pub height: u64,
+    pub target_height: u64,
+    // 0.0 to 1.0
+    pub progress: f64,
+    pub blocks_per_sec: f64,
+    pub eta_secs: Option<u64>,
+    pub peers: u32,
+}
+
+#[derive(Default)]
+struct Tracker {
+    samples: VecDeque<(Instant, u64)>,
+    // Timestamp of block 1, the anchor for the average interval
+    first_block_ts: Option<i64>,
+    // Seconds per block over the whole chain; 0 until known
+    avg_interval: f64,
+    last: Option<SyncProgress>,
+}
+
+#[derive(Default)]
+pub struct SyncState {
+    tracker: Mutex<Tracker>,
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+async fn block(app: &AppHandle, height: u64) -> Result<BlockHeader, String> {
+    crate::daemon_json(app, "GET", &format!("/api/block/{}", height), None).await
+}
+
+fn avg_interval(height: u64, tip_ts: i64, first_ts: Option<i64>) -> f64 {
+    match first_ts {
+        Some(first) if height > 1 && tip_ts > first => (tip_ts - first) as f64 / (height - 1) as f64,
+        _ => 0.0,
+    }
+}
+
+fn estimate(
+    stats: &DaemonStats,
+    tip_ts: i64,
+    first_ts: Option<i64>,
+    samples: &VecDeque<(Instant, u64)>,
+) -> SyncProgress {
+    let height = stats.chain_height;
+    // At height 0 or 1 there is no interval to average yet, and the tip is
+    // likely the genesis block with a very old timestamp
+    let interval = match avg_interval(height, tip_ts, first_ts) {
+        i if i > 0.0 => i,
+        _ => FALLBACK_BLOCK_SECS,
+    };
+    let behind = (now_secs() - tip_ts).max(0) as f64;
+    let missing = if behind > interval * CAUGHT_UP_INTERVALS {
+        (behind / interval).round() as u64
+    } else {
+        0
+    };
+    let target_height = height + missing;
+
+    let blocks_per_sec = match (samples.front(), samples.back()) {
+        (Some((t0, h0)), Some((t1, h1))) if t1 > t0 && h1 >= h0 => {
+            (h1 - h0) as f64 / t1.duration_since(*t0).as_secs_f64()
+        }
+        _ => 0.0,
+    };
+    let eta_secs = if missing == 0 {
+        Some(0)
+    } else if blocks_per_sec > 0.0 {
+        Some((missing as f64 / blocks_per_sec).ceil() as u64)
+    } else {
+        None
+    };
+
+    SyncProgress {
+        syncing: stats.syncing || missing > 0,
+        height,
+        target_height,
+        progress: if target_height == 0 { 1.0 } else { height as f64 / target_height as f64 },
+        blocks_per_sec,
+        eta_secs,
+        peers: stats.peers,
+    }
+}
+
+async fn sample(app: &AppHandle) -> Result<SyncProgress, String> {
+    let stats: DaemonStats = crate::daemon_json(app, "GET", "/api/status", None).await?;
+    let tip = block(app, stats.chain_height).await?;
+
+    let state = app.state::<SyncState>();
+    let first_ts = state.tracker.lock().map_err(|e| e.to_string())?.first_block_ts;
+    let first_ts = match first_ts {
+        Some(ts) => Some(ts),
+        None if stats.chain_height >= 1 => block(app, 1).await.ok().map(|b| b.timestamp),
+        None => None,
+    };
+
+    let mut tracker = state.tracker.lock().map_err(|e| e.to_string())?;
+    tracker.first_block_ts = first_ts;
+    // A reset or reorg to a lower height invalidates the rate
+    if tracker.samples.back().is_some_and(|(_, h)| *h > stats.chain_height) {
+        tracker.samples.clear();
+    }
+    tracker.samples.push_back((Instant::now(), stats.chain_height));
+    while tracker.samples.front().is_some_and(|(t, _)| t.elapsed() > RATE_WINDOW) {
+        tracker.samples.pop_front();
+    }
+    tracker.avg_interval = avg_interval(stats.chain_height, tip.timestamp, first_ts);
+    let progress = estimate(&stats, tip.timestamp, first_ts, &tracker.samples);
+    tracker.last = Some(progress.clone());
+    Ok(progress)
+}
+
+fn reset(app: &AppHandle) {
+    if let Ok(mut tracker) = app.state::<SyncState>().tracker.lock() {
+        *tracker = Tracker::default();
+    }
+}
+
+// Average block interval in seconds, once the tracker has seen the chain
+pub fn avg_block_interval(app: &AppHandle) -> Option<f64> {
+    let interval = app.state::<SyncState>().tracker.lock().ok()?.avg_interval;
+    (interval > 0.0).then_some(interval)
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            match sample(&app).await {
+                Ok(progress) => {
+                    let _ = app.emit("sync-progress", progress);
+                }
+                // Daemon down or restarting; start over when it returns
+                Err(_) => reset(&app),
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_sync_progress(state: State<'_, SyncState>) -> Option<SyncProgress> {
+    state.tracker.lock().ok().and_then(|t| t.last.clone())
+}
@@ -0,0 +1,433 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::models::MiningStatus;
+
+// Keeps the miner under a CPU temperature and load ceiling by stepping the
+// thread count down one at a time, and back up towards where it was once
+// things have cooled for a while. Whoever else changes the thread count
+// (the user, the scheduler) sets a new baseline. Sensors are read from
+// sysfs/procfs paths held in Sensors, so the controller can be driven by
+// fake files; on platforms without them it does nothing.
+
+const TICK: Duration = Duration::from_secs(10);
+// Below the limits by this much before threads come back
+const TEMP_HYSTERESIS_C: f64 = 5.0;
+const LOAD_HYSTERESIS_PCT: f64 = 10.0;
+// Consecutive cool readings before each step up
+const COOL_TICKS: u32 = 6;
+
+const CPU_ZONE_TYPES: &[&str] = &["x86_pkg_temp", "cpu", "coretemp", "k10temp", "soc", "package"];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThrottleSettings {
+    pub enabled: bool,
+    pub max_temp_c: f64,
+    // 0 disables the load limit
+    pub max_load_pct: f64,
+}
+
+impl Default for ThrottleSettings {
+    fn default() -> Self {
+        Self { enabled: false, max_temp_c: 80.0, max_load_pct: 0.0 }
+    }
+}
+
+#[derive(Clone)]
+pub struct Sensors {
+    pub thermal_root: PathBuf,
+    pub proc_stat: PathBuf,
+}
+
+impl Sensors {
+    pub fn system() -> Self {
+        Self {
+            thermal_root: PathBuf::from("/sys/class/thermal"),
+            proc_stat: PathBuf::from("/proc/stat"),
+        }
+    }
+
+    // Hottest CPU zone in Celsius, or the hottest zone of any kind when none
+    // is labelled as the CPU
+    pub fn temperature(&self) -> Option<f64> {
+        let mut cpu: Option<f64> = None;
+        let mut any: Option<f64> = None;
+        for entry in std::fs::read_dir(&self.thermal_root).ok()?.flatten() {
+            let dir = entry.path();
+            if !entry.file_name().to_string_lossy().starts_with("thermal_zone") {
+                continue;
+            }
+            let Some(milli) = std::fs::read_to_string(dir.join("temp"))
+                .ok()
+                .and_then(|s| s.trim().parse::<f64>().ok())
+            else {
+                continue;
+            };
+            let celsius = milli / 1000.0;
+            let kind = std::fs::read_to_string(dir.join("type")).unwrap_or_default().to_lowercase();
+            if CPU_ZONE_TYPES.iter().any(|t| kind.contains(t)) {
+                cpu = Some(cpu.map_or(celsius, |c| c.max(celsius)));
+            }
+            any = Some(any.map_or(celsius, |c| c.max(celsius)));
+        }
+        cpu.or(any)
+    }
+
+    // (busy, total) jiffies from the aggregate cpu line
+    pub fn cpu_times(&self) -> Option<(u64, u64)> {
+        let text = std::fs::read_to_string(&self.proc_stat).ok()?;
+        let line = text.lines().find(|l| l.starts_with("cpu "))?;
+        let fields: Vec<u64> = line.split_whitespace().skip(1).take(8).filter_map(|f| f.parse().ok()).collect();
+        if fields.len() < 4 {
+            return None;
+        }
+        let total: u64 = fields.iter().sum();
+        // idle + iowait
+        let idle = fields[3] + fields.get(4).copied().unwrap_or(0);
+        Some((total - idle, total))
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct Reading {
+    pub temp_c: Option<f64>,
+    pub load_pct: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct Controller {
+    last_cpu: Option<(u64, u64)>,
+    // Thread count before throttling began
+    baseline: Option<u32>,
+    // What we last asked the daemon for
+    applied: Option<u32>,
+    cool_ticks: u32,
+}
+
+impl Controller {
+    pub fn read(&mut self, sensors: &Sensors) -> Reading {
+        let cpu = sensors.cpu_times();
+        let load_pct = match (self.last_cpu, cpu) {
+            (Some((b0, t0)), Some((b1, t1))) if t1 > t0 => {
+                Some(b1.saturating_sub(b0) as f64 * 100.0 / (t1 - t0) as f64)
+            }
+            _ => None,
+        };
+        self.last_cpu = cpu;
+        Reading { temp_c: sensors.temperature(), load_pct }
+    }
+
+    pub fn reset(&mut self) {
+        self.baseline = None;
+        self.applied = None;
+        self.cool_ticks = 0;
+    }
+
+    // The thread count to switch to, if any
+    pub fn step(&mut self, reading: &Reading, threads: u32, limits: &ThrottleSettings) -> Option<u32> {
+        if self.applied.is_some_and(|a| a != threads) {
+            self.reset();
+        }
+        let over_temp = reading.temp_c.is_some_and(|t| t > limits.max_temp_c);
+        let over_load = limits.max_load_pct > 0.0 && reading.load_pct.is_some_and(|l| l > limits.max_load_pct);
+        if over_temp || over_load {
+            self.cool_ticks = 0;
+            if threads <= 1 {
+                return None;
+            }
+            self.baseline.get_or_insert(threads);
+            self.applied = Some(threads - 1);
+            return self.applied;
+        }
+
+        let baseline = self.baseline?;
+        let cool_temp = reading.temp_c.is_none_or(|t| t < limits.max_temp_c - TEMP_HYSTERESIS_C);
+        let cool_load = limits.max_load_pct <= 0.0
+            || reading.load_pct.is_none_or(|l| l < limits.max_load_pct - LOAD_HYSTERESIS_PCT);
+        if !(cool_temp && cool_load) {
+            self.cool_ticks = 0;
+            return None;
+        }
+        self.cool_ticks += 1;
+        if self.cool_ticks < COOL_TICKS {
+            return None;
+        }
+        self.cool_ticks = 0;
+        let next = (threads + 1).min(baseline);
+        if next >= baseline {
+            self.reset();
+        } else {
+            self.applied = Some(next);
+        }
+        (next != threads).then_some(next)
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct ThrottleEvent {
+    pub from: u32,
+    pub to: u32,
+    pub reading: Reading,
+}
+
+pub struct ThrottleState {
+    settings: Mutex<ThrottleSettings>,
+    controller: Mutex<Controller>,
+    last: Mutex<Option<Reading>>,
+}
+
+impl ThrottleState {
+    pub fn new(settings: ThrottleSettings) -> Self {
+        Self {
+            settings: Mutex::new(settings),
+            controller: Mutex::new(Controller::default()),
+            last: Mutex::new(None),
+        }
+    }
+
+    fn settings(&self) -> ThrottleSettings {
+        self.settings.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+fn log_event(app: &AppHandle, event: &ThrottleEvent) {
+    let fmt = |v: Option<f64>, unit: &str| v.map(|v| format!("{:.1}{}", v, unit)).unwrap_or_else(|| "n/a".to_string());
+    let line = format!(
+        "{} threads {} -> {} (temp {}, load {})\n",
+        chrono::Local::now().to_rfc3339(),
+        event.from,
+        event.to,
+        fmt(event.reading.temp_c, "C"),
+        fmt(event.reading.load_pct, "%"),
+    );
+    let result = crate::get_log_dir(app).and_then(|dir| {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create log dir: {}", e))?;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("mining-throttle.log"))
+            .and_then(|mut f| f.write_all(line.as_bytes()))
+            .map_err(|e| format!("Failed to write throttle log: {}", e))
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e);
+    }
+}
+
+async fn tick(app: &AppHandle, sensors: &Sensors) -> Result<(), String> {
+    let state = app.state::<ThrottleState>();
+    let settings = state.settings();
+    let reading = state.controller.lock().map_err(|e| format!("Lock error: {}", e))?.read(sensors);
+    *state.last.lock().map_err(|e| format!("Lock error: {}", e))? = Some(reading.clone());
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let status: MiningStatus = crate::daemon_json(app, "GET", "/api/mining", None).await?;
+    let next = {
+        let mut controller = state.controller.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if !status.running {
+            controller.reset();
+            return Ok(());
+        }
+        controller.step(&reading, status.threads, &settings)
+    };
+    let Some(to) = next else { return Ok(()) };
+
+    let body = serde_json::json!({ "threads": to });
+    crate::daemon_request(app, "POST", "/api/mining/threads", Some(body.to_string())).await?;
+    let event = ThrottleEvent { from: status.threads, to, reading };
+    log_event(app, &event);
+    let _ = app.emit("mining-throttle", event);
+    Ok(())
+}
+
+pub fn spawn(app: AppHandle, sensors: Sensors) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if tick(&app, &sensors).await.is_err() {
+                if let Ok(mut controller) = app.state::<ThrottleState>().controller.lock() {
+                    controller.reset();
+                }
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_throttle_settings(state: State<'_, ThrottleState>) -> ThrottleSettings {
+    state.settings()
+}
+
+#[tauri::command]
+pub fn set_throttle_settings(
+    app: AppHandle,
+    state: State<'_, ThrottleState>,
+    settings: ThrottleSettings,
+) -> Result<(), String> {
+    if settings.max_temp_c <= 0.0 || settings.max_load_pct < 0.0 || settings.max_load_pct > 100.0 {
+        return Err("Invalid throttle limits".to_string());
+    }
+    crate::settings::update(&app, |s| s.throttle = settings.clone())?;
+    *state.settings.lock().map_err(|e| format!("Lock error: {}", e))? = settings;
+    Ok(())
+}
+
+// Latest sensor reading, for showing temperature and load next to the limits
+#[tauri::command]
+pub fn get_cpu_reading(state: State<'_, ThrottleState>) -> Option<Reading> {
+    state.last.lock().ok().and_then(|r| r.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A throwaway stand-in for /sys/class/thermal and /proc/stat
+    struct FakeSys {
+        root: PathBuf,
+        sensors: Sensors,
+    }
+
+    impl FakeSys {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("blocknet-throttle-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(root.join("thermal")).unwrap();
+            let sensors = Sensors { thermal_root: root.join("thermal"), proc_stat: root.join("stat") };
+            Self { root, sensors }
+        }
+
+        fn zone(&self, n: u32, kind: &str, celsius: f64) {
+            let dir = self.sensors.thermal_root.join(format!("thermal_zone{}", n));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("type"), format!("{}\n", kind)).unwrap();
+            std::fs::write(dir.join("temp"), format!("{}\n", (celsius * 1000.0) as i64)).unwrap();
+        }
+
+        // user nice system idle iowait irq softirq steal
+        fn stat(&self, busy: u64, idle: u64) {
+            let text = format!("cpu  {} 0 0 {} 0 0 0 0 0 0\ncpu0 {} 0 0 {} 0 0 0 0 0 0\n", busy, idle, busy, idle);
+            std::fs::write(&self.sensors.proc_stat, text).unwrap();
+        }
+    }
+
+    impl Drop for FakeSys {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    fn limits() -> ThrottleSettings {
+        ThrottleSettings { enabled: true, max_temp_c: 80.0, max_load_pct: 0.0 }
+    }
+
+    fn temp(c: f64) -> Reading {
+        Reading { temp_c: Some(c), load_pct: None }
+    }
+
+    #[test]
+    fn temperature_prefers_cpu_zones() {
+        let sys = FakeSys::new("cpu-zones");
+        sys.zone(0, "acpitz", 95.0);
+        sys.zone(1, "x86_pkg_temp", 61.5);
+        sys.zone(2, "coretemp", 64.0);
+        assert_eq!(sys.sensors.temperature(), Some(64.0));
+    }
+
+    #[test]
+    fn temperature_falls_back_to_any_zone() {
+        let sys = FakeSys::new("any-zone");
+        sys.zone(0, "acpitz", 47.0);
+        sys.zone(1, "iwlwifi", 52.0);
+        assert_eq!(sys.sensors.temperature(), Some(52.0));
+    }
+
+    #[test]
+    fn missing_sensors_read_as_none() {
+        let sys = FakeSys::new("missing");
+        assert_eq!(sys.sensors.temperature(), None);
+        assert_eq!(sys.sensors.cpu_times(), None);
+    }
+
+    #[test]
+    fn load_comes_from_the_difference_between_reads() {
+        let sys = FakeSys::new("load");
+        let mut controller = Controller::default();
+        sys.stat(100, 900);
+        assert_eq!(controller.read(&sys.sensors).load_pct, None);
+        sys.stat(175, 925);
+        assert_eq!(controller.read(&sys.sensors).load_pct, Some(75.0));
+    }
+
+    #[test]
+    fn steps_down_while_hot_and_not_below_one() {
+        let mut controller = Controller::default();
+        assert_eq!(controller.step(&temp(85.0), 3, &limits()), Some(2));
+        assert_eq!(controller.step(&temp(85.0), 2, &limits()), Some(1));
+        assert_eq!(controller.step(&temp(85.0), 1, &limits()), None);
+    }
+
+    #[test]
+    fn steps_back_up_to_the_baseline_after_cooling() {
+        let mut controller = Controller::default();
+        assert_eq!(controller.step(&temp(85.0), 4, &limits()), Some(3));
+        assert_eq!(controller.step(&temp(85.0), 3, &limits()), Some(2));
+        let mut threads = 2;
+        for _ in 0..2 {
+            for _ in 1..COOL_TICKS {
+                assert_eq!(controller.step(&temp(60.0), threads, &limits()), None);
+            }
+            threads = controller.step(&temp(60.0), threads, &limits()).unwrap();
+        }
+        assert_eq!(threads, 4);
+        // Back at the baseline, cool readings change nothing
+        for _ in 0..COOL_TICKS * 2 {
+            assert_eq!(controller.step(&temp(60.0), 4, &limits()), None);
+        }
+    }
+
+    #[test]
+    fn hysteresis_band_holds_the_count() {
+        let mut controller = Controller::default();
+        assert_eq!(controller.step(&temp(85.0), 4, &limits()), Some(3));
+        for _ in 0..COOL_TICKS * 2 {
+            assert_eq!(controller.step(&temp(78.0), 3, &limits()), None);
+        }
+    }
+
+    #[test]
+    fn outside_change_sets_a_new_baseline() {
+        let mut controller = Controller::default();
+        assert_eq!(controller.step(&temp(85.0), 4, &limits()), Some(3));
+        // Someone else picked 2 threads; cooling must not climb past it
+        for _ in 0..COOL_TICKS * 2 {
+            assert_eq!(controller.step(&temp(60.0), 2, &limits()), None);
+        }
+    }
+
+    #[test]
+    fn load_limit_throttles_from_fake_proc_stat() {
+        let sys = FakeSys::new("load-limit");
+        let limits = ThrottleSettings { enabled: true, max_temp_c: 80.0, max_load_pct: 50.0 };
+        let mut controller = Controller::default();
+        sys.zone(0, "cpu", 40.0);
+        sys.stat(0, 0);
+        controller.read(&sys.sensors);
+        sys.stat(90, 10);
+        let reading = controller.read(&sys.sensors);
+        assert_eq!(controller.step(&reading, 4, &limits), Some(3));
+        // Under the limit but inside the hysteresis band
+        sys.stat(135, 55);
+        let reading = controller.read(&sys.sensors);
+        assert_eq!(reading.load_pct, Some(50.0));
+        assert_eq!(controller.step(&reading, 3, &limits), None);
+    }
+}
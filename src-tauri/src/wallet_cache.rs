@@ -0,0 +1,274 @@
+use std::sync::Mutex;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::models::{Balance, BlockHeader, DaemonStats, OutputEntry};
+use crate::store;
+
+// Last known balance and outputs per wallet, in wallet-data/<wallet>/cache.json,
+// so there is something to show while the daemon syncs or is not answering.
+// The snapshot is encrypted with ChaCha20-Poly1305 under a key derived with
+// Argon2id from the wallet password; the key only lives in memory between
+// unlock and lock. Each snapshot records the block it was taken at, and is
+// thrown away once that block is no longer part of the chain.
+
+const VERSION: u32 = 1;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Envelope {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snapshot {
+    pub balance: Option<Balance>,
+    pub outputs: Option<Vec<OutputEntry>>,
+    pub chain_height: u64,
+    pub best_hash: String,
+    pub saved_at: u64,
+}
+
+struct Unlocked {
+    wallet: String,
+    salt: [u8; 16],
+    key: [u8; 32],
+    snapshot: Snapshot,
+}
+
+#[derive(Default)]
+pub struct WalletCacheState {
+    unlocked: Mutex<Option<Unlocked>>,
+}
+
+#[derive(Serialize)]
+pub struct BalanceView {
+    #[serde(flatten)]
+    pub balance: Balance,
+    pub cached: bool,
+}
+
+fn cache_path(app: &AppHandle, wallet: &str) -> Result<std::path::PathBuf, String> {
+    Ok(crate::get_wallet_data_dir_for(app, wallet)?.join("cache.json"))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// An odd trailing digit fails the slice, and so the whole decode
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok()).collect()
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// DaemonStats.best_hash is a prefix of the full block hash
+fn same_block(a: &str, b: &str) -> bool {
+    !a.is_empty() && !b.is_empty() && (a.starts_with(b) || b.starts_with(a))
+}
+
+fn derive_key(password: &str, salt: &[u8; 16]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive cache key: {}", e))?;
+    Ok(key)
+}
+
+fn seal(key: &[u8; 32], salt: &[u8; 16], snapshot: &Snapshot) -> Result<Envelope, String> {
+    let plain = serde_json::to_vec(snapshot).map_err(|e| format!("Failed to serialize cache: {}", e))?;
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), plain.as_slice())
+        .map_err(|_| "Failed to encrypt cache".to_string())?;
+    Ok(Envelope { version: VERSION, salt: to_hex(salt), nonce: to_hex(&nonce), ciphertext: to_hex(&ciphertext) })
+}
+
+fn open(key: &[u8; 32], envelope: &Envelope) -> Option<Snapshot> {
+    let nonce = from_hex(&envelope.nonce).filter(|n| n.len() == 12)?;
+    let ciphertext = from_hex(&envelope.ciphertext)?;
+    let plain = ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .ok()?;
+    serde_json::from_slice(&plain).ok()
+}
+
+// Called by api_call once the daemon has accepted the password for the
+// active wallet. A file that does not open with this password (an older
+// format, a wallet re-imported under a new password) is started over.
+pub async fn unlock(app: &AppHandle, password: String) {
+    let Ok(wallet) = crate::get_active_wallet_name(app) else { return };
+    let envelope: Envelope = cache_path(app, &wallet)
+        .and_then(|p| store::load_json(&p))
+        .unwrap_or_default();
+    let stored_salt = from_hex(&envelope.salt)
+        .filter(|_| envelope.version == VERSION)
+        .and_then(|s| <[u8; 16]>::try_from(s).ok());
+    let salt = stored_salt.unwrap_or_else(|| {
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        salt
+    });
+
+    let derived = tauri::async_runtime::spawn_blocking(move || derive_key(&password, &salt)).await;
+    let key = match derived {
+        Ok(Ok(key)) => key,
+        Ok(Err(e)) => return eprintln!("{}", e),
+        Err(e) => return eprintln!("Failed to derive cache key: {}", e),
+    };
+    let snapshot = stored_salt.and_then(|_| open(&key, &envelope)).unwrap_or_default();
+    if let Ok(mut unlocked) = app.state::<WalletCacheState>().unlocked.lock() {
+        *unlocked = Some(Unlocked { wallet, salt, key, snapshot });
+    }
+
+    // The chain may have moved on while the app was closed
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        check(&app).await;
+    });
+}
+
+// Called when the wallet locks
+pub fn forget(app: &AppHandle) {
+    if let Ok(mut unlocked) = app.state::<WalletCacheState>().unlocked.lock() {
+        *unlocked = None;
+    }
+}
+
+fn snapshot(app: &AppHandle) -> Option<Snapshot> {
+    let wallet = crate::get_active_wallet_name(app).ok()?;
+    let state = app.state::<WalletCacheState>();
+    let unlocked = state.unlocked.lock().ok()?;
+    unlocked.as_ref().filter(|u| u.wallet == wallet).map(|u| u.snapshot.clone())
+}
+
+fn save<F: FnOnce(&mut Snapshot)>(app: &AppHandle, stats: &DaemonStats, f: F) {
+    let Ok(wallet) = crate::get_active_wallet_name(app) else { return };
+    let sealed = {
+        let state = app.state::<WalletCacheState>();
+        let Ok(mut unlocked) = state.unlocked.lock() else { return };
+        let Some(u) = unlocked.as_mut().filter(|u| u.wallet == wallet) else { return };
+        f(&mut u.snapshot);
+        u.snapshot.chain_height = stats.chain_height;
+        u.snapshot.best_hash = stats.best_hash.clone();
+        u.snapshot.saved_at = now_secs();
+        seal(&u.key, &u.salt, &u.snapshot)
+    };
+    let result = sealed.and_then(|envelope| store::save_json(&cache_path(app, &wallet)?, &envelope));
+    if let Err(e) = result {
+        eprintln!("Failed to save wallet cache: {}", e);
+    }
+}
+
+// Asks the daemon through `fetch` and caches the answer. While the daemon
+// is syncing or behind the snapshot its answer is incomplete, so the cached
+// value is served instead, as it is when the daemon does not answer at all.
+// The flag says whether the value came from the cache.
+pub async fn fresh_or_cached<T, Fut>(
+    app: &AppHandle,
+    fetch: Fut,
+    get: fn(&Snapshot) -> Option<T>,
+    put: fn(&mut Snapshot, T),
+) -> Result<(T, bool), String>
+where
+    T: Clone,
+    Fut: std::future::Future<Output = Result<T, String>>,
+{
+    let snap = snapshot(app);
+    let cached = snap.as_ref().and_then(get);
+    let fresh = async {
+        let stats: DaemonStats = crate::daemon_json(app, "GET", "/api/status", None).await?;
+        let behind = stats.syncing || snap.as_ref().is_some_and(|s| stats.chain_height < s.chain_height);
+        if behind && cached.is_some() {
+            return Err("Daemon is syncing".to_string());
+        }
+        let value = fetch.await?;
+        if !behind {
+            save(app, &stats, |s| put(s, value.clone()));
+        }
+        Ok(value)
+    }
+    .await;
+    match (fresh, cached) {
+        (Ok(value), _) => Ok((value, false)),
+        (Err(_), Some(value)) => Ok((value, true)),
+        (Err(e), None) => Err(e),
+    }
+}
+
+fn invalidate(app: &AppHandle, wallet: &str) {
+    if let Ok(mut unlocked) = app.state::<WalletCacheState>().unlocked.lock() {
+        if let Some(u) = unlocked.as_mut().filter(|u| u.wallet == wallet) {
+            u.snapshot = Snapshot::default();
+        }
+    }
+    if let Ok(path) = cache_path(app, wallet) {
+        let _ = std::fs::remove_file(path);
+    }
+    let _ = app.emit("wallet-cache-invalidated", wallet.to_string());
+}
+
+// Drops the snapshot when the block it was taken at has been replaced. A
+// daemon that is still below that height (syncing) cannot tell yet.
+pub async fn check(app: &AppHandle) {
+    let Some(snap) = snapshot(app) else { return };
+    if snap.best_hash.is_empty() {
+        return;
+    }
+    let Ok(stats) = crate::daemon_json::<DaemonStats>(app, "GET", "/api/status", None).await else { return };
+    if stats.chain_height < snap.chain_height {
+        return;
+    }
+    let replaced = if stats.chain_height == snap.chain_height {
+        !same_block(&stats.best_hash, &snap.best_hash)
+    } else {
+        let path = format!("/api/block/{}", snap.chain_height);
+        match crate::daemon_json::<BlockHeader>(app, "GET", &path, None).await {
+            Ok(block) => !same_block(&block.hash, &snap.best_hash),
+            Err(_) => return,
+        }
+    };
+    if replaced {
+        if let Ok(wallet) = crate::get_active_wallet_name(app) {
+            invalidate(app, &wallet);
+        }
+    }
+}
+
+// Called from events for every new_block
+pub fn on_new_block(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        check(&app).await;
+    });
+}
+
+#[tauri::command]
+pub async fn get_balance(app: AppHandle) -> Result<BalanceView, String> {
+    let fetch = crate::daemon_json::<Balance>(&app, "GET", "/api/wallet/balance", None);
+    let (balance, cached) = fresh_or_cached(&app, fetch, |s| s.balance.clone(), |s, b| s.balance = Some(b)).await?;
+    Ok(BalanceView { balance, cached })
+}
+
+// Whatever is cached, without asking the daemon; for the first paint
+// after unlock
+#[tauri::command]
+pub fn get_cached_wallet(app: AppHandle) -> Option<Snapshot> {
+    snapshot(&app).filter(|s| s.saved_at > 0)
+}